@@ -28,6 +28,14 @@ impl Event {
             Self::RunComplete(event) => event.to_jsonline(),
         }
     }
+
+    /// Read an event written by [`Event::to_jsonline`]
+    ///
+    /// Unknown fields are ignored.
+    #[cfg(feature = "json")]
+    pub fn from_jsonline(line: &str) -> Result<Self, crate::ParseEventError> {
+        crate::parse::parse_event(line)
+    }
 }
 
 impl From<DiscoverStart> for Event {
@@ -83,6 +91,36 @@ impl From<RunComplete> for Event {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct DiscoverStart {
+    /// Wall-clock time the test binary started
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub started_at_s: Option<Timestamp>,
+    /// Path to the test binary
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub binary: Option<String>,
+    /// Arguments the test binary was run with, excluding the binary itself
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub args: Vec<String>,
+    /// Working directory of the test binary
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub current_dir: Option<String>,
+    /// Target triple the test binary was built for, e.g. `x86_64-unknown-linux-gnu`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub target: Option<String>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -102,6 +140,48 @@ impl DiscoverStart {
         buffer.keyval_sep().unwrap();
         buffer.value("discover_start").unwrap();
 
+        if let Some(started_at_s) = self.started_at_s {
+            buffer.val_sep().unwrap();
+            buffer.key("started_at_s").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(String::from(started_at_s)).unwrap();
+        }
+
+        if let Some(binary) = &self.binary {
+            buffer.val_sep().unwrap();
+            buffer.key("binary").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(binary).unwrap();
+        }
+
+        if !self.args.is_empty() {
+            buffer.val_sep().unwrap();
+            buffer.key("args").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.open_array().unwrap();
+            for (i, arg) in self.args.iter().enumerate() {
+                if i != 0 {
+                    buffer.val_sep().unwrap();
+                }
+                buffer.value(arg).unwrap();
+            }
+            buffer.close_array().unwrap();
+        }
+
+        if let Some(current_dir) = &self.current_dir {
+            buffer.val_sep().unwrap();
+            buffer.key("current_dir").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(current_dir).unwrap();
+        }
+
+        if let Some(target) = &self.target {
+            buffer.val_sep().unwrap();
+            buffer.key("target").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(target).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -132,6 +212,18 @@ pub struct DiscoverCase {
         serde(default = "true_default", skip_serializing_if = "is_true")
     )]
     pub selected: bool,
+    /// Parameters the case was generated from
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")
+    )]
+    pub params: std::collections::BTreeMap<String, String>,
+    /// Categories the case belongs to, e.g. `slow`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub tags: Vec<String>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -170,6 +262,36 @@ impl DiscoverCase {
             buffer.value(self.selected).unwrap();
         }
 
+        if !self.params.is_empty() {
+            buffer.val_sep().unwrap();
+            buffer.key("params").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.open_object().unwrap();
+            for (i, (key, value)) in self.params.iter().enumerate() {
+                if i != 0 {
+                    buffer.val_sep().unwrap();
+                }
+                buffer.key(key).unwrap();
+                buffer.keyval_sep().unwrap();
+                buffer.value(value).unwrap();
+            }
+            buffer.close_object().unwrap();
+        }
+
+        if !self.tags.is_empty() {
+            buffer.val_sep().unwrap();
+            buffer.key("tags").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.open_array().unwrap();
+            for (i, tag) in self.tags.iter().enumerate() {
+                if i != 0 {
+                    buffer.val_sep().unwrap();
+                }
+                buffer.value(tag).unwrap();
+            }
+            buffer.close_array().unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -225,6 +347,18 @@ impl DiscoverComplete {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct RunStart {
+    /// Seed that each case's seed is derived from
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub seed: Option<Seed>,
+    /// Most threads cases are run on at once
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub threads: Option<usize>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -244,6 +378,20 @@ impl RunStart {
         buffer.keyval_sep().unwrap();
         buffer.value("run_start").unwrap();
 
+        if let Some(seed) = self.seed {
+            buffer.val_sep().unwrap();
+            buffer.key("seed").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(String::from(seed)).unwrap();
+        }
+
+        if let Some(threads) = self.threads {
+            buffer.val_sep().unwrap();
+            buffer.key("threads").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(threads as u64).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -263,6 +411,12 @@ impl RunStart {
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct CaseStart {
     pub name: String,
+    /// The case that created this one while running, if not found during discovery
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub parent: Option<String>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -287,6 +441,13 @@ impl CaseStart {
         buffer.keyval_sep().unwrap();
         buffer.value(&self.name).unwrap();
 
+        if let Some(parent) = &self.parent {
+            buffer.val_sep().unwrap();
+            buffer.key("parent").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(parent).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -312,6 +473,48 @@ pub struct CaseMessage {
         serde(default, skip_serializing_if = "Option::is_none")
     )]
     pub message: Option<String>,
+    /// Where the failure happened, as `file:line:column`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub location: Option<String>,
+    /// Errors that led to `message`, from [`std::error::Error::source`]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub causes: Vec<String>,
+    /// Value the case expected
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub expected: Option<String>,
+    /// Value the case got instead of `expected`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub actual: Option<String>,
+    /// Left side of a failed comparison that doesn't say which side was expected, e.g. `assert_eq!`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub left: Option<String>,
+    /// Right side of the comparison `left` is from
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub right: Option<String>,
+    /// Stack backtrace of a panic, if enabled with `RUST_BACKTRACE`
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub backtrace: Option<String>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -348,6 +551,62 @@ impl CaseMessage {
             buffer.value(message).unwrap();
         }
 
+        if let Some(location) = &self.location {
+            buffer.val_sep().unwrap();
+            buffer.key("location").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(location).unwrap();
+        }
+
+        if !self.causes.is_empty() {
+            buffer.val_sep().unwrap();
+            buffer.key("causes").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.open_array().unwrap();
+            for (i, cause) in self.causes.iter().enumerate() {
+                if i != 0 {
+                    buffer.val_sep().unwrap();
+                }
+                buffer.value(cause).unwrap();
+            }
+            buffer.close_array().unwrap();
+        }
+
+        if let Some(expected) = &self.expected {
+            buffer.val_sep().unwrap();
+            buffer.key("expected").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(expected).unwrap();
+        }
+
+        if let Some(actual) = &self.actual {
+            buffer.val_sep().unwrap();
+            buffer.key("actual").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(actual).unwrap();
+        }
+
+        if let Some(left) = &self.left {
+            buffer.val_sep().unwrap();
+            buffer.key("left").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(left).unwrap();
+        }
+
+        if let Some(right) = &self.right {
+            buffer.val_sep().unwrap();
+            buffer.key("right").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(right).unwrap();
+        }
+
+        if let Some(backtrace) = &self.backtrace {
+            buffer.val_sep().unwrap();
+            buffer.key("backtrace").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(backtrace).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -367,6 +626,12 @@ impl CaseMessage {
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct CaseComplete {
     pub name: String,
+    /// Scratch directory retained for debugging a failed case
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub scratch_dir: Option<String>,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -391,6 +656,13 @@ impl CaseComplete {
         buffer.keyval_sep().unwrap();
         buffer.value(&self.name).unwrap();
 
+        if let Some(scratch_dir) = &self.scratch_dir {
+            buffer.val_sep().unwrap();
+            buffer.key("scratch_dir").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(scratch_dir).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -409,6 +681,9 @@ impl CaseComplete {
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub struct RunComplete {
+    /// Whether the run was stopped early by a signal, e.g. Ctrl-C
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "is_false"))]
+    pub interrupted: bool,
     #[cfg_attr(
         feature = "serde",
         serde(default, skip_serializing_if = "Option::is_none")
@@ -428,6 +703,13 @@ impl RunComplete {
         buffer.keyval_sep().unwrap();
         buffer.value("run_complete").unwrap();
 
+        if self.interrupted {
+            buffer.val_sep().unwrap();
+            buffer.key("interrupted").unwrap();
+            buffer.keyval_sep().unwrap();
+            buffer.value(self.interrupted).unwrap();
+        }
+
         if let Some(elapsed_s) = self.elapsed_s {
             buffer.val_sep().unwrap();
             buffer.key("elapsed_s").unwrap();
@@ -451,6 +733,11 @@ fn is_true(yes: &bool) -> bool {
     *yes
 }
 
+#[cfg(feature = "serde")]
+fn is_false(yes: &bool) -> bool {
+    !*yes
+}
+
 #[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -481,15 +768,22 @@ impl RunMode {
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum MessageKind {
     // Highest precedent items for determining test status last
-    Error,
+    // The case stopped early because the run is being torn down
+    Cancelled,
     Ignored,
+    // The case never started because the run stopped early
+    NotRun,
+    // A failure is never hidden by how the case stopped
+    Error,
 }
 
 impl MessageKind {
     pub fn as_str(&self) -> &str {
         match self {
             Self::Error => "error",
+            Self::Cancelled => "cancelled",
             Self::Ignored => "ignored",
+            Self::NotRun => "not_run",
         }
     }
 }
@@ -531,6 +825,85 @@ impl From<Elapsed> for String {
     }
 }
 
+/// Wall-clock time, in seconds since the Unix epoch
+///
+/// Written as a string with millisecond precision, e.g. `"1700000000.123"`.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String"))]
+#[cfg_attr(feature = "serde", serde(try_from = "String"))]
+pub struct Timestamp(pub std::time::Duration);
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{:03}", self.0.as_secs(), self.0.subsec_millis())
+    }
+}
+
+impl std::str::FromStr for Timestamp {
+    type Err = std::num::ParseFloatError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        let secs: f64 = src.parse()?;
+        // Round to the written precision so a round-trip is lossless
+        let millis = (secs * 1000.0).round() as u64;
+        Ok(Timestamp(std::time::Duration::from_millis(millis)))
+    }
+}
+
+impl TryFrom<String> for Timestamp {
+    type Error = std::num::ParseFloatError;
+
+    fn try_from(inner: String) -> Result<Self, Self::Error> {
+        inner.parse()
+    }
+}
+
+impl From<Timestamp> for String {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.to_string()
+    }
+}
+
+/// Seed for randomizing cases
+///
+/// Written as a string as consumers may not support all 64-bit integers.
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "unstable-schema", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String"))]
+#[cfg_attr(feature = "serde", serde(try_from = "String"))]
+pub struct Seed(pub u64);
+
+impl std::fmt::Display for Seed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::str::FromStr for Seed {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(src: &str) -> Result<Self, Self::Err> {
+        Ok(Seed(src.parse()?))
+    }
+}
+
+impl TryFrom<String> for Seed {
+    type Error = std::num::ParseIntError;
+
+    fn try_from(inner: String) -> Result<Self, Self::Error> {
+        inner.parse()
+    }
+}
+
+impl From<Seed> for String {
+    fn from(seed: Seed) -> Self {
+        seed.0.to_string()
+    }
+}
+
 #[cfg(feature = "unstable-schema")]
 #[test]
 fn dump_event_schema() {
@@ -6,11 +6,17 @@
 #![allow(clippy::todo)]
 
 pub mod event;
+#[cfg(feature = "json")]
+mod parse;
 
 pub use event::Elapsed;
 pub use event::Event;
 pub use event::MessageKind;
 pub use event::RunMode;
+pub use event::Seed;
+pub use event::Timestamp;
+#[cfg(feature = "json")]
+pub use parse::ParseEventError;
 
 #[doc = include_str!("../README.md")]
 #[cfg(doctest)]
@@ -0,0 +1,620 @@
+use crate::event::{
+    CaseComplete, CaseMessage, CaseStart, DiscoverCase, DiscoverComplete, DiscoverStart, Elapsed,
+    Event, MessageKind, RunComplete, RunMode, RunStart, Seed, Timestamp,
+};
+
+/// Nested arrays and objects beyond this are rejected rather than risking the stack
+const MAX_DEPTH: usize = 64;
+
+/// Invalid jsonline, see [`Event::from_jsonline`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseEventError {
+    message: String,
+    column: usize,
+}
+
+impl std::fmt::Display for ParseEventError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at column {}", self.message, self.column)
+    }
+}
+
+impl std::error::Error for ParseEventError {}
+
+pub(crate) fn parse_event(line: &str) -> Result<Event, ParseEventError> {
+    let mut parser = Parser {
+        input: line,
+        pos: 0,
+    };
+    parser.skip_whitespace();
+    let column = parser.pos + 1;
+    let value = parser.value(0)?;
+    parser.skip_whitespace();
+    if !parser.rest().is_empty() {
+        return Err(parser.error("expected the end of the line"));
+    }
+    let Value::Object(fields) = value else {
+        return Err(ParseEventError {
+            message: "expected an object".to_owned(),
+            column,
+        });
+    };
+
+    let mut fields = Fields { fields, column };
+    let (event, event_column) = fields.required_string("event")?;
+    let event = match event.as_str() {
+        "discover_start" => DiscoverStart {
+            started_at_s: fields.timestamp("started_at_s")?,
+            binary: fields.string("binary")?,
+            args: fields.strings("args")?,
+            current_dir: fields.string("current_dir")?,
+            target: fields.string("target")?,
+            elapsed_s: fields.elapsed("elapsed_s")?,
+        }
+        .into(),
+        "discover_case" => DiscoverCase {
+            name: fields.required_string("name")?.0,
+            mode: fields.run_mode("mode")?.unwrap_or_default(),
+            selected: fields.bool("selected")?.unwrap_or(true),
+            params: fields.string_map("params")?,
+            tags: fields.strings("tags")?,
+            elapsed_s: fields.elapsed("elapsed_s")?,
+        }
+        .into(),
+        "discover_complete" => DiscoverComplete {
+            elapsed_s: fields.elapsed("elapsed_s")?,
+        }
+        .into(),
+        "run_start" => RunStart {
+            seed: fields.seed("seed")?,
+            threads: fields.usize("threads")?,
+            elapsed_s: fields.elapsed("elapsed_s")?,
+        }
+        .into(),
+        "case_start" => CaseStart {
+            name: fields.required_string("name")?.0,
+            parent: fields.string("parent")?,
+            elapsed_s: fields.elapsed("elapsed_s")?,
+        }
+        .into(),
+        "case_message" => CaseMessage {
+            name: fields.required_string("name")?.0,
+            kind: fields.required_message_kind("kind")?,
+            message: fields.string("message")?,
+            location: fields.string("location")?,
+            causes: fields.strings("causes")?,
+            expected: fields.string("expected")?,
+            actual: fields.string("actual")?,
+            left: fields.string("left")?,
+            right: fields.string("right")?,
+            backtrace: fields.string("backtrace")?,
+            elapsed_s: fields.elapsed("elapsed_s")?,
+        }
+        .into(),
+        "case_complete" => CaseComplete {
+            name: fields.required_string("name")?.0,
+            scratch_dir: fields.string("scratch_dir")?,
+            elapsed_s: fields.elapsed("elapsed_s")?,
+        }
+        .into(),
+        "run_complete" => RunComplete {
+            interrupted: fields.bool("interrupted")?.unwrap_or(false),
+            elapsed_s: fields.elapsed("elapsed_s")?,
+        }
+        .into(),
+        _ => {
+            return Err(ParseEventError {
+                message: format!("unknown event `{event}`"),
+                column: event_column,
+            });
+        }
+    };
+    // Any remaining fields are unknown and ignored so newer producers can add fields
+    Ok(event)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    /// Kept as written so each field can decide what range it accepts
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<Field>),
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "a boolean",
+            Self::Number(_) => "a number",
+            Self::String(_) => "a string",
+            Self::Array(_) => "an array",
+            Self::Object(_) => "an object",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Field {
+    key: String,
+    /// Where `value` starts, for reporting errors
+    column: usize,
+    value: Value,
+}
+
+/// Fields of an event, removed as they are decoded
+struct Fields {
+    fields: Vec<Field>,
+    /// Where the event starts, for reporting missing fields
+    column: usize,
+}
+
+impl Fields {
+    /// Remove `key`, treating `null` the same as a missing field
+    fn take(&mut self, key: &str) -> Option<(Value, usize)> {
+        let i = self.fields.iter().position(|field| field.key == key)?;
+        let field = self.fields.remove(i);
+        match field.value {
+            Value::Null => None,
+            value => Some((value, field.column)),
+        }
+    }
+
+    fn required_string(&mut self, key: &str) -> Result<(String, usize), ParseEventError> {
+        let Some((value, column)) = self.take(key) else {
+            return Err(ParseEventError {
+                message: format!("missing field `{key}`"),
+                column: self.column,
+            });
+        };
+        Ok((expect_string(key, value, column)?, column))
+    }
+
+    fn string(&mut self, key: &str) -> Result<Option<String>, ParseEventError> {
+        self.take(key)
+            .map(|(value, column)| expect_string(key, value, column))
+            .transpose()
+    }
+
+    fn strings(&mut self, key: &str) -> Result<Vec<String>, ParseEventError> {
+        let Some((value, column)) = self.take(key) else {
+            return Ok(Vec::new());
+        };
+        let Value::Array(values) = value else {
+            return Err(expected(key, "an array of strings", &value, column));
+        };
+        values
+            .into_iter()
+            .map(|value| match value {
+                Value::String(value) => Ok(value),
+                value => Err(expected(key, "an array of strings", &value, column)),
+            })
+            .collect()
+    }
+
+    fn string_map(
+        &mut self,
+        key: &str,
+    ) -> Result<std::collections::BTreeMap<String, String>, ParseEventError> {
+        let Some((value, column)) = self.take(key) else {
+            return Ok(Default::default());
+        };
+        let Value::Object(fields) = value else {
+            return Err(expected(key, "an object of strings", &value, column));
+        };
+        fields
+            .into_iter()
+            .map(|field| match field.value {
+                Value::String(value) => Ok((field.key, value)),
+                value => Err(expected(key, "an object of strings", &value, field.column)),
+            })
+            .collect()
+    }
+
+    fn bool(&mut self, key: &str) -> Result<Option<bool>, ParseEventError> {
+        self.take(key)
+            .map(|(value, column)| match value {
+                Value::Bool(value) => Ok(value),
+                value => Err(expected(key, "a boolean", &value, column)),
+            })
+            .transpose()
+    }
+
+    fn usize(&mut self, key: &str) -> Result<Option<usize>, ParseEventError> {
+        self.take(key)
+            .map(|(value, column)| match value {
+                Value::Number(number) => number
+                    .parse::<usize>()
+                    .map_err(|_| invalid(key, &number, column)),
+                value => Err(expected(key, "a number", &value, column)),
+            })
+            .transpose()
+    }
+
+    fn elapsed(&mut self, key: &str) -> Result<Option<Elapsed>, ParseEventError> {
+        self.take(key)
+            .map(|(value, column)| {
+                let value = expect_string(key, value, column)?;
+                // `Duration::from_secs_f64` panics on negative or out-of-range values
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .and_then(|secs| std::time::Duration::try_from_secs_f64(secs).ok())
+                    .map(Elapsed)
+                    .ok_or_else(|| invalid(key, &value, column))
+            })
+            .transpose()
+    }
+
+    fn timestamp(&mut self, key: &str) -> Result<Option<Timestamp>, ParseEventError> {
+        self.take(key)
+            .map(|(value, column)| {
+                let value = expect_string(key, value, column)?;
+                // Reject what `Duration` can't represent before rounding to milliseconds
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|secs| std::time::Duration::try_from_secs_f64(*secs).is_ok())
+                    .and_then(|_| value.parse::<Timestamp>().ok())
+                    .ok_or_else(|| invalid(key, &value, column))
+            })
+            .transpose()
+    }
+
+    fn seed(&mut self, key: &str) -> Result<Option<Seed>, ParseEventError> {
+        self.take(key)
+            .map(|(value, column)| {
+                let value = expect_string(key, value, column)?;
+                value
+                    .parse::<Seed>()
+                    .map_err(|_| invalid(key, &value, column))
+            })
+            .transpose()
+    }
+
+    fn run_mode(&mut self, key: &str) -> Result<Option<RunMode>, ParseEventError> {
+        self.take(key)
+            .map(|(value, column)| {
+                let value = expect_string(key, value, column)?;
+                [RunMode::Test, RunMode::Bench]
+                    .into_iter()
+                    .find(|mode| mode.as_str() == value)
+                    .ok_or_else(|| invalid(key, &value, column))
+            })
+            .transpose()
+    }
+
+    fn required_message_kind(&mut self, key: &str) -> Result<MessageKind, ParseEventError> {
+        let (value, column) = self.required_string(key)?;
+        [
+            MessageKind::Cancelled,
+            MessageKind::Ignored,
+            MessageKind::NotRun,
+            MessageKind::Error,
+        ]
+        .into_iter()
+        .find(|kind| kind.as_str() == value)
+        .ok_or_else(|| invalid(key, &value, column))
+    }
+}
+
+fn expect_string(key: &str, value: Value, column: usize) -> Result<String, ParseEventError> {
+    match value {
+        Value::String(value) => Ok(value),
+        value => Err(expected(key, "a string", &value, column)),
+    }
+}
+
+fn expected(key: &str, expected: &str, actual: &Value, column: usize) -> ParseEventError {
+    ParseEventError {
+        message: format!("expected {expected} for `{key}`, found {}", actual.kind()),
+        column,
+    }
+}
+
+fn invalid(key: &str, value: &str, column: usize) -> ParseEventError {
+    ParseEventError {
+        message: format!("invalid value `{value}` for `{key}`"),
+        column,
+    }
+}
+
+struct Parser<'s> {
+    input: &'s str,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn value(&mut self, depth: usize) -> Result<Value, ParseEventError> {
+        match self.peek() {
+            Some('{') => self.object(depth),
+            Some('[') => self.array(depth),
+            Some('"') => self.string().map(Value::String),
+            Some('-' | '0'..='9') => self.number(),
+            Some(_) if self.eat("true") => Ok(Value::Bool(true)),
+            Some(_) if self.eat("false") => Ok(Value::Bool(false)),
+            Some(_) if self.eat("null") => Ok(Value::Null),
+            _ => Err(self.error("expected a value")),
+        }
+    }
+
+    fn object(&mut self, depth: usize) -> Result<Value, ParseEventError> {
+        let depth = self.nest(depth)?;
+        self.pos += 1;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.eat("}") {
+            return Ok(Value::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a key"));
+            }
+            let key = self.string()?;
+            self.skip_whitespace();
+            if !self.eat(":") {
+                return Err(self.error("expected `:`"));
+            }
+            self.skip_whitespace();
+            let column = self.pos + 1;
+            let value = self.value(depth)?;
+            fields.push(Field { key, column, value });
+            self.skip_whitespace();
+            if self.eat("}") {
+                return Ok(Value::Object(fields));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected `,` or `}`"));
+            }
+        }
+    }
+
+    fn array(&mut self, depth: usize) -> Result<Value, ParseEventError> {
+        let depth = self.nest(depth)?;
+        self.pos += 1;
+        let mut values = Vec::new();
+        self.skip_whitespace();
+        if self.eat("]") {
+            return Ok(Value::Array(values));
+        }
+        loop {
+            self.skip_whitespace();
+            values.push(self.value(depth)?);
+            self.skip_whitespace();
+            if self.eat("]") {
+                return Ok(Value::Array(values));
+            }
+            if !self.eat(",") {
+                return Err(self.error("expected `,` or `]`"));
+            }
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseEventError> {
+        self.pos += 1;
+        let mut value = String::new();
+        loop {
+            let start = self.pos;
+            let Some(c) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(value),
+                '\\' => {
+                    let Some(escape) = self.peek() else {
+                        return Err(self.error("unterminated string"));
+                    };
+                    self.pos += escape.len_utf8();
+                    let c = match escape {
+                        '"' => '"',
+                        '\\' => '\\',
+                        '/' => '/',
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => self.unicode_escape(start)?,
+                        _ => {
+                            self.pos = start;
+                            return Err(self.error("invalid escape"));
+                        }
+                    };
+                    value.push(c);
+                }
+                '\u{0}'..='\u{1f}' => {
+                    self.pos = start;
+                    return Err(self.error("control character in string"));
+                }
+                c => value.push(c),
+            }
+        }
+    }
+
+    /// Decode the rest of a `\u` escape, including the low half of a surrogate pair
+    fn unicode_escape(&mut self, start: usize) -> Result<char, ParseEventError> {
+        let high = self.hex4(start)?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            if !self.eat("\\u") {
+                self.pos = start;
+                return Err(self.error("unpaired surrogate in escape"));
+            }
+            let low = self.hex4(start)?;
+            if !(0xDC00..0xE000).contains(&low) {
+                self.pos = start;
+                return Err(self.error("unpaired surrogate in escape"));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or_else(|| {
+            self.pos = start;
+            self.error("unpaired surrogate in escape")
+        })
+    }
+
+    fn hex4(&mut self, start: usize) -> Result<u32, ParseEventError> {
+        let digits = self.rest().get(..4).unwrap_or_default();
+        if digits.len() != 4 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            self.pos = start;
+            return Err(self.error("invalid escape"));
+        }
+        self.pos += 4;
+        Ok(u32::from_str_radix(digits, 16).unwrap())
+    }
+
+    fn number(&mut self) -> Result<Value, ParseEventError> {
+        let start = self.pos;
+        self.eat("-");
+        if !self.eat("0") && self.digits() == 0 {
+            return Err(self.error("expected a digit"));
+        }
+        if self.eat(".") && self.digits() == 0 {
+            return Err(self.error("expected a digit"));
+        }
+        if self.eat("e") || self.eat("E") {
+            let _ = self.eat("+") || self.eat("-");
+            if self.digits() == 0 {
+                return Err(self.error("expected a digit"));
+            }
+        }
+        Ok(Value::Number(self.input[start..self.pos].to_owned()))
+    }
+
+    fn digits(&mut self) -> usize {
+        let count = self
+            .rest()
+            .bytes()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        self.pos += count;
+        count
+    }
+
+    fn nest(&self, depth: usize) -> Result<usize, ParseEventError> {
+        if MAX_DEPTH <= depth {
+            return Err(self.error("too deeply nested"));
+        }
+        Ok(depth + 1)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+        self.pos += rest.len() - trimmed.len();
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        if self.rest().starts_with(expected) {
+            self.pos += expected.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> ParseEventError {
+        ParseEventError {
+            message: message.to_owned(),
+            column: self.pos + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(s: &str) -> Result<String, String> {
+        parse_event(s)
+            .map(|event| event.to_jsonline())
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn unknown_fields() {
+        assert_eq!(
+            parse(r#"{"event":"case_start","name":"cat","retries":[1,{"a":null}],"parent":null}"#),
+            Ok(r#"{"event":"case_start","name":"cat"}"#.to_owned())
+        );
+    }
+
+    #[test]
+    fn whitespace_and_escapes() {
+        assert_eq!(
+            parse(" { \"name\" : \"a\\\"b\\\\c\\/\\u00e9\\ud83d\\ude00\\n\" , \"event\":\"case_complete\" } \r\n"),
+            Ok(r#"{"event":"case_complete","name":"a\"b\\c/é😀\n"}"#.to_owned())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse(""), Err("expected a value at column 1".to_owned()));
+        assert_eq!(
+            parse(r#"{"event":"run_start""#),
+            Err("expected `,` or `}` at column 21".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"run_start"} {}"#),
+            Err("expected the end of the line at column 23".to_owned())
+        );
+        assert_eq!(
+            parse(r#"["run_start"]"#),
+            Err("expected an object at column 1".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"name":"cat"}"#),
+            Err("missing field `event` at column 1".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"case_end","name":"cat"}"#),
+            Err("unknown event `case_end` at column 10".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"case_message","name":"cat","kind":"warning"}"#),
+            Err("invalid value `warning` for `kind` at column 45".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"run_start","elapsed_s":1.5}"#),
+            Err("expected a string for `elapsed_s`, found a number at column 34".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"run_start","elapsed_s":"-1"}"#),
+            Err("invalid value `-1` for `elapsed_s` at column 34".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"discover_case","name":"cat","tags":["a",1]}"#),
+            Err("expected an array of strings for `tags`, found a number at column 46".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"case_start","name":"c\at"}"#),
+            Err("invalid escape at column 32".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"case_start","name":"\ud83d"}"#),
+            Err("unpaired surrogate in escape at column 31".to_owned())
+        );
+        assert_eq!(
+            parse(r#"{"event":"discover_start","threads":01}"#),
+            Err("expected `,` or `}` at column 38".to_owned())
+        );
+        assert_eq!(
+            parse(&format!("{}{}", "[".repeat(100), "]".repeat(100))),
+            Err("too deeply nested at column 65".to_owned())
+        );
+    }
+}
@@ -1,4 +1,3 @@
-#![cfg(feature = "serde")]
 #![cfg(feature = "json")]
 
 use snapbox::prelude::*;
@@ -8,25 +7,58 @@ use snapbox::str;
 fn t(input: impl Into<libtest_json::Event>, snapshot: impl IntoData) {
     let input = input.into();
     let actual_encoded = input.to_jsonline();
-    let expected_encoded = serde_json::to_string(&input).unwrap();
-    snapbox::assert_data_eq!(&actual_encoded, expected_encoded.raw());
+    #[cfg(feature = "serde")]
+    {
+        let expected_encoded = serde_json::to_string(&input).unwrap();
+        snapbox::assert_data_eq!(&actual_encoded, expected_encoded.raw());
+
+        let _ = serde_json::from_str::<libtest_json::Event>(&actual_encoded).unwrap();
+    }
     snapbox::assert_data_eq!(&actual_encoded, snapshot.raw());
 
-    let _ = serde_json::from_str::<libtest_json::Event>(&actual_encoded).unwrap();
+    let decoded = libtest_json::Event::from_jsonline(&actual_encoded).unwrap();
+    snapbox::assert_data_eq!(decoded.to_jsonline(), actual_encoded.raw());
 }
 
 #[test]
 fn discover_start() {
     t(
-        libtest_json::event::DiscoverStart { elapsed_s: None },
+        libtest_json::event::DiscoverStart {
+            started_at_s: None,
+            binary: None,
+            args: vec![],
+            current_dir: None,
+            target: None,
+            elapsed_s: None,
+        },
         str![[r#"{"event":"discover_start"}"#]],
     );
     t(
         libtest_json::event::DiscoverStart {
+            started_at_s: None,
+            binary: None,
+            args: vec![],
+            current_dir: None,
+            target: None,
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
         str![[r#"{"event":"discover_start","elapsed_s":"0"}"#]],
     );
+    t(
+        libtest_json::event::DiscoverStart {
+            started_at_s: Some(libtest_json::Timestamp(std::time::Duration::from_millis(
+                1_700_000_000_123,
+            ))),
+            binary: Some("target/debug/deps/foo-1234".to_owned()),
+            args: vec!["--format".to_owned(), "json".to_owned()],
+            current_dir: Some("/src/foo".to_owned()),
+            target: Some("x86_64-unknown-linux-gnu".to_owned()),
+            elapsed_s: None,
+        },
+        str![[
+            r#"{"event":"discover_start","started_at_s":"1700000000.123","binary":"target/debug/deps/foo-1234","args":["--format","json"],"current_dir":"/src/foo","target":"x86_64-unknown-linux-gnu"}"#
+        ]],
+    );
 }
 
 #[test]
@@ -36,6 +68,8 @@ fn discover_case() {
             name: "Hello\tworld!".to_owned(),
             mode: libtest_json::RunMode::Test,
             selected: true,
+            params: Default::default(),
+            tags: Default::default(),
             elapsed_s: None,
         },
         str![[r#"{"event":"discover_case","name":"Hello\tworld!"}"#]],
@@ -46,12 +80,47 @@ fn discover_case() {
             name: "Hello\tworld!".to_owned(),
             mode: libtest_json::RunMode::Bench,
             selected: false,
+            params: Default::default(),
+            tags: Default::default(),
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
         str![[
             r#"{"event":"discover_case","name":"Hello\tworld!","mode":"bench","selected":false,"elapsed_s":"0"}"#
         ]],
     );
+
+    t(
+        libtest_json::event::DiscoverCase {
+            name: "parse::ascii::short".to_owned(),
+            mode: libtest_json::RunMode::Test,
+            selected: true,
+            params: [
+                ("input".to_owned(), "ascii".to_owned()),
+                ("length".to_owned(), "short".to_owned()),
+            ]
+            .into_iter()
+            .collect(),
+            tags: Default::default(),
+            elapsed_s: None,
+        },
+        str![[
+            r#"{"event":"discover_case","name":"parse::ascii::short","params":{"input":"ascii","length":"short"}}"#
+        ]],
+    );
+
+    t(
+        libtest_json::event::DiscoverCase {
+            name: "net::fetch".to_owned(),
+            mode: libtest_json::RunMode::Test,
+            selected: true,
+            params: Default::default(),
+            tags: vec!["slow".to_owned(), "requires-docker".to_owned()],
+            elapsed_s: None,
+        },
+        str![[
+            r#"{"event":"discover_case","name":"net::fetch","tags":["slow","requires-docker"]}"#
+        ]],
+    );
 }
 
 #[test]
@@ -72,14 +141,22 @@ fn discover_complete() {
 #[test]
 fn suite_start() {
     t(
-        libtest_json::event::RunStart { elapsed_s: None },
+        libtest_json::event::RunStart {
+            seed: None,
+            threads: None,
+            elapsed_s: None,
+        },
         str![[r#"{"event":"run_start"}"#]],
     );
     t(
         libtest_json::event::RunStart {
+            seed: Some(libtest_json::Seed(u64::MAX)),
+            threads: Some(4),
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
-        str![[r#"{"event":"run_start","elapsed_s":"0"}"#]],
+        str![[
+            r#"{"event":"run_start","seed":"18446744073709551615","threads":4,"elapsed_s":"0"}"#
+        ]],
     );
 }
 
@@ -88,16 +165,20 @@ fn case_start() {
     t(
         libtest_json::event::CaseStart {
             name: "Hello\tworld!".to_owned(),
+            parent: None,
             elapsed_s: None,
         },
         str![[r#"{"event":"case_start","name":"Hello\tworld!"}"#]],
     );
     t(
         libtest_json::event::CaseStart {
-            name: "Hello\tworld!".to_owned(),
+            name: "Hello\tworld!::sub".to_owned(),
+            parent: Some("Hello\tworld!".to_owned()),
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
-        str![[r#"{"event":"case_start","name":"Hello\tworld!","elapsed_s":"0"}"#]],
+        str![[
+            r#"{"event":"case_start","name":"Hello\tworld!::sub","parent":"Hello\tworld!","elapsed_s":"0"}"#
+        ]],
     );
 }
 
@@ -108,6 +189,13 @@ fn case_message() {
             name: "Hello\tworld!".to_owned(),
             kind: libtest_json::MessageKind::Error,
             message: None,
+            location: None,
+            causes: Default::default(),
+            expected: None,
+            actual: None,
+            left: None,
+            right: None,
+            backtrace: None,
             elapsed_s: None,
         },
         str![[r#"{"event":"case_message","name":"Hello\tworld!","kind":"error"}"#]],
@@ -118,12 +206,72 @@ fn case_message() {
             name: "Hello\tworld!".to_owned(),
             kind: libtest_json::MessageKind::Ignored,
             message: Some("This\tfailed".to_owned()),
+            location: None,
+            causes: Default::default(),
+            expected: None,
+            actual: None,
+            left: None,
+            right: None,
+            backtrace: None,
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
         str![[
             r#"{"event":"case_message","name":"Hello\tworld!","kind":"ignored","message":"This\tfailed","elapsed_s":"0"}"#
         ]],
     );
+
+    t(
+        libtest_json::event::CaseMessage {
+            name: "Hello\tworld!".to_owned(),
+            kind: libtest_json::MessageKind::Cancelled,
+            message: None,
+            location: None,
+            causes: Default::default(),
+            expected: None,
+            actual: None,
+            left: None,
+            right: None,
+            backtrace: None,
+            elapsed_s: None,
+        },
+        str![[r#"{"event":"case_message","name":"Hello\tworld!","kind":"cancelled"}"#]],
+    );
+
+    t(
+        libtest_json::event::CaseMessage {
+            name: "Hello\tworld!".to_owned(),
+            kind: libtest_json::MessageKind::NotRun,
+            message: None,
+            location: None,
+            causes: Default::default(),
+            expected: None,
+            actual: None,
+            left: None,
+            right: None,
+            backtrace: None,
+            elapsed_s: None,
+        },
+        str![[r#"{"event":"case_message","name":"Hello\tworld!","kind":"not_run"}"#]],
+    );
+
+    t(
+        libtest_json::event::CaseMessage {
+            name: "parse".to_owned(),
+            kind: libtest_json::MessageKind::Error,
+            message: Some("test panicked: assertion `left == right` failed".to_owned()),
+            location: Some("src/lib.rs:10:5".to_owned()),
+            causes: vec!["invalid digit".to_owned()],
+            expected: Some("42".to_owned()),
+            actual: Some("4".to_owned()),
+            left: Some("4".to_owned()),
+            right: Some("42".to_owned()),
+            backtrace: Some("   0: parse::{{closure}}\n             at src/lib.rs:10:5".to_owned()),
+            elapsed_s: None,
+        },
+        str![[
+            r#"{"event":"case_message","name":"parse","kind":"error","message":"test panicked: assertion `left == right` failed","location":"src/lib.rs:10:5","causes":["invalid digit"],"expected":"42","actual":"4","left":"4","right":"42","backtrace":"   0: parse::{{closure}}\n             at src/lib.rs:10:5"}"#
+        ]],
+    );
 }
 
 #[test]
@@ -131,6 +279,7 @@ fn case_complete() {
     t(
         libtest_json::event::CaseComplete {
             name: "Hello\tworld!".to_owned(),
+            scratch_dir: None,
             elapsed_s: None,
         },
         str![[r#"{"event":"case_complete","name":"Hello\tworld!"}"#]],
@@ -139,23 +288,30 @@ fn case_complete() {
     t(
         libtest_json::event::CaseComplete {
             name: "Hello\tworld!".to_owned(),
+            scratch_dir: Some("/tmp/Hello_world_".to_owned()),
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
-        str![[r#"{"event":"case_complete","name":"Hello\tworld!","elapsed_s":"0"}"#]],
+        str![[
+            r#"{"event":"case_complete","name":"Hello\tworld!","scratch_dir":"/tmp/Hello_world_","elapsed_s":"0"}"#
+        ]],
     );
 }
 
 #[test]
 fn suite_complete() {
     t(
-        libtest_json::event::RunComplete { elapsed_s: None },
+        libtest_json::event::RunComplete {
+            interrupted: false,
+            elapsed_s: None,
+        },
         str![[r#"{"event":"run_complete"}"#]],
     );
 
     t(
         libtest_json::event::RunComplete {
+            interrupted: true,
             elapsed_s: Some(libtest_json::Elapsed(Default::default())),
         },
-        str![[r#"{"event":"run_complete","elapsed_s":"0"}"#]],
+        str![[r#"{"event":"run_complete","interrupted":true,"elapsed_s":"0"}"#]],
     );
 }
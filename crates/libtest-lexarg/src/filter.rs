@@ -0,0 +1,327 @@
+/// Expression selecting tests, see [`TestOpts::filter_exprs`][crate::TestOpts::filter_exprs]
+///
+/// Predicates are combined with `not`/`!`, `and`/`&`, and `or`/`|`, in order of precedence, and
+/// grouped with parentheses:
+/// - `all`: every test
+/// - `name(GLOB)`: the whole name matches `GLOB`, where `*` matches any run of characters and `?`
+///   any one character
+/// - `module(PATH)`: the test is within the module `PATH`, e.g. `module(parser::expr)`
+/// - `kind(KIND)`: the test is a `unit`, `integration`, `doc`, or `unknown` test
+/// - `source(GLOB)`: the test's source file matches `GLOB`
+/// - `ignored`: the test is marked as ignored
+///
+/// Arguments may contain parentheses as long as they are balanced, e.g. `name(case(1)*)`.
+///
+/// For example, `module(parser) and not (name(*::slow_*) or ignored)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterExpr {
+    All,
+    Name(String),
+    Module(String),
+    Kind(String),
+    Source(String),
+    Ignored,
+    Not(Box<FilterExpr>),
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+/// Nested `not`s and parentheses beyond this are rejected rather than risking the stack
+const MAX_DEPTH: usize = 64;
+
+/// Values accepted by `kind(KIND)`
+pub const FILTER_KINDS: &[&str] = &["unit", "integration", "doc", "unknown"];
+
+impl std::str::FromStr for FilterExpr {
+    type Err = FilterExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = ExprParser {
+            input: s,
+            pos: 0,
+            depth: 0,
+        };
+        let expr = parser.or()?;
+        parser.skip_whitespace();
+        if !parser.rest().is_empty() {
+            return Err(parser.error("expected `and`, `or`, or the end of the expression"));
+        }
+        Ok(expr)
+    }
+}
+
+/// Invalid [`FilterExpr`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FilterExprError {
+    message: String,
+    column: usize,
+}
+
+impl std::fmt::Display for FilterExprError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at column {}", self.message, self.column)
+    }
+}
+
+impl std::error::Error for FilterExprError {}
+
+struct ExprParser<'s> {
+    input: &'s str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'s> ExprParser<'s> {
+    fn or(&mut self) -> Result<FilterExpr, FilterExprError> {
+        let mut expr = self.and()?;
+        while self.eat_operator("or", '|') {
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.and()?));
+        }
+        Ok(expr)
+    }
+
+    fn and(&mut self) -> Result<FilterExpr, FilterExprError> {
+        let mut expr = self.not()?;
+        while self.eat_operator("and", '&') {
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.not()?));
+        }
+        Ok(expr)
+    }
+
+    fn not(&mut self) -> Result<FilterExpr, FilterExprError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.eat_operator("not", '!') {
+            self.nest(start)?;
+            let expr = self.not()?;
+            self.depth -= 1;
+            Ok(FilterExpr::Not(Box::new(expr)))
+        } else {
+            self.atom()
+        }
+    }
+
+    fn atom(&mut self) -> Result<FilterExpr, FilterExprError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.eat('(') {
+            self.nest(start)?;
+            let expr = self.or()?;
+            self.depth -= 1;
+            self.skip_whitespace();
+            if !self.eat(')') {
+                return Err(self.error("expected `)`"));
+            }
+            return Ok(expr);
+        }
+
+        let predicate = self.peek_word();
+        if predicate.is_empty() {
+            return Err(self.error("expected a predicate, `not`, or `(`"));
+        }
+        self.pos += predicate.len();
+        self.skip_whitespace();
+        let arg = if self.eat('(') {
+            let Some(len) = self.find_closing() else {
+                return Err(self.error("expected `)`"));
+            };
+            let arg = self.rest()[..len].trim();
+            self.pos += len + 1;
+            Some(arg).filter(|arg| !arg.is_empty())
+        } else {
+            None
+        };
+
+        let error = |message: String| FilterExprError {
+            message,
+            column: start + 1,
+        };
+        let expr = match (predicate, arg) {
+            ("all", None) => FilterExpr::All,
+            ("ignored", None) => FilterExpr::Ignored,
+            ("name", Some(glob)) => FilterExpr::Name(glob.to_owned()),
+            ("module", Some(path)) => FilterExpr::Module(path.to_owned()),
+            ("kind", Some(kind)) => {
+                if !FILTER_KINDS.contains(&kind) {
+                    let expected = FILTER_KINDS
+                        .iter()
+                        .map(|k| format!("`{k}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    return Err(error(format!(
+                        "unknown test kind `{kind}`, expected one of {expected}"
+                    )));
+                }
+                FilterExpr::Kind(kind.to_owned())
+            }
+            ("source", Some(glob)) => FilterExpr::Source(glob.to_owned()),
+            ("all" | "ignored", Some(_)) => {
+                return Err(error(format!("`{predicate}` takes no argument")));
+            }
+            ("name" | "module" | "kind" | "source", None) => {
+                return Err(error(format!("`{predicate}` requires an argument")));
+            }
+            _ => {
+                return Err(error(format!("unknown predicate `{predicate}`")));
+            }
+        };
+        Ok(expr)
+    }
+
+    /// Consume the operator spelled as `word` or `symbol`
+    fn eat_operator(&mut self, word: &str, symbol: char) -> bool {
+        if self.peek_word() == word {
+            self.pos += word.len();
+            true
+        } else {
+            self.eat(symbol)
+        }
+    }
+
+    /// Offset of the `)` closing an argument, skipping over balanced parentheses within it
+    fn find_closing(&self) -> Option<usize> {
+        let mut open = 0;
+        for (i, c) in self.rest().char_indices() {
+            match c {
+                '(' => open += 1,
+                ')' if open == 0 => return Some(i),
+                ')' => open -= 1,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Enter a `not` or parentheses starting at `start`
+    fn nest(&mut self, start: usize) -> Result<(), FilterExprError> {
+        if MAX_DEPTH <= self.depth {
+            return Err(FilterExprError {
+                message: "too deeply nested".to_owned(),
+                column: start + 1,
+            });
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.rest().starts_with(c);
+        if found {
+            self.pos += c.len_utf8();
+        }
+        found
+    }
+
+    fn peek_word(&mut self) -> &'s str {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len());
+        &rest[..len]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn rest(&self) -> &'s str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> FilterExprError {
+        FilterExprError {
+            message: message.to_owned(),
+            column: self.pos + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(s: &str) -> Result<FilterExpr, String> {
+        s.parse::<FilterExpr>().map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn precedence() {
+        use FilterExpr::*;
+
+        assert_eq!(
+            parse("!ignored | name(a::*) & kind(unit)"),
+            Ok(Or(
+                Box::new(Not(Box::new(Ignored))),
+                Box::new(And(
+                    Box::new(Name("a::*".to_owned())),
+                    Box::new(Kind("unit".to_owned()))
+                ))
+            ))
+        );
+        assert_eq!(
+            parse("not (all or ignored) and source( tests/*.rs )"),
+            Ok(And(
+                Box::new(Not(Box::new(Or(Box::new(All), Box::new(Ignored))))),
+                Box::new(Source("tests/*.rs".to_owned()))
+            ))
+        );
+    }
+
+    #[test]
+    fn nested_argument() {
+        assert_eq!(
+            parse("name(case(1)*) or name(())"),
+            Ok(FilterExpr::Or(
+                Box::new(FilterExpr::Name("case(1)*".to_owned())),
+                Box::new(FilterExpr::Name("()".to_owned()))
+            ))
+        );
+        assert_eq!(
+            parse("name(a(b)"),
+            Err("expected `)` at column 6".to_owned())
+        );
+    }
+
+    #[test]
+    fn too_deep() {
+        assert!(parse(&format!("{}all{}", "(".repeat(64), ")".repeat(64))).is_ok());
+        assert_eq!(
+            parse(&"(".repeat(60_000)),
+            Err("too deeply nested at column 65".to_owned())
+        );
+        assert_eq!(
+            parse(&"!".repeat(60_000)),
+            Err("too deeply nested at column 65".to_owned())
+        );
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            parse("nam(foo)"),
+            Err("unknown predicate `nam` at column 1".to_owned())
+        );
+        assert_eq!(
+            parse("all and kind(bench)"),
+            Err(
+                "unknown test kind `bench`, expected one of `unit`, `integration`, `doc`, `unknown` at column 9"
+                    .to_owned()
+            )
+        );
+        assert_eq!(
+            parse("module()"),
+            Err("`module` requires an argument at column 1".to_owned())
+        );
+        assert_eq!(parse("(all"), Err("expected `)` at column 5".to_owned()));
+        assert_eq!(
+            parse("all ignored"),
+            Err("expected `and`, `or`, or the end of the expression at column 5".to_owned())
+        );
+        assert_eq!(
+            parse(""),
+            Err("expected a predicate, `not`, or `(` at column 1".to_owned())
+        );
+    }
+}
@@ -13,6 +13,10 @@
 #![forbid(unsafe_code)]
 #![warn(missing_debug_implementations, elided_lifetimes_in_paths)]
 
+mod filter;
+
+pub use filter::*;
+
 use lexarg::Arg;
 use lexarg_error::LexError;
 
@@ -32,61 +36,70 @@ pub struct TestOpts {
     pub show_output: bool,
     pub color: ColorConfig,
     pub format: OutputFormat,
+    /// Also write json events here, independent of `format`
+    pub json_logfile: Option<std::path::PathBuf>,
     pub test_threads: Option<std::num::NonZeroUsize>,
     pub skip: Vec<String>,
+    /// Only run tests matching any of these, in addition to `filters` and `skip`
+    pub filter_exprs: Vec<FilterExpr>,
+    /// Only run tests with any of these tags
+    pub tags: Vec<String>,
+    /// Skip tests with any of these tags
+    pub skip_tags: Vec<String>,
     /// Stop at first failing test.
     /// May run a few more tests due to threading, but will
     /// abort as soon as possible.
     pub fail_fast: bool,
+    /// Durations of previous runs, for starting the longest tests first when running in parallel.
+    /// Updated once the run completes.
+    pub timings: Option<std::path::PathBuf>,
+    /// Only run the tests that failed in the previous run, in the order they were recorded
+    pub rerun_failed: bool,
+    /// Outcomes and durations of previous runs, for spotting flaky tests.
+    /// Appended to once the run completes, reported on by `--list`.
+    pub history: Option<std::path::PathBuf>,
+    /// Seed that each test's seed is derived from, random if unset
+    pub seed: Option<u64>,
+    /// Report how long each test took, and the slowest tests
+    pub report_time: bool,
     pub allowed_unstable: Vec<String>,
 }
 
 /// Whether ignored test should be run or not (see [`TestOpts::run_ignored`])
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub enum RunIgnored {
     Yes,
+    #[default]
     No,
     /// Run only ignored tests
     Only,
 }
 
-impl Default for RunIgnored {
-    fn default() -> Self {
-        Self::No
-    }
-}
-
 /// Whether should console output be colored or not (see [`TestOpts::color`])
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Default, Debug)]
 pub enum ColorConfig {
+    #[default]
     AutoColor,
     AlwaysColor,
     NeverColor,
 }
 
-impl Default for ColorConfig {
-    fn default() -> Self {
-        Self::AutoColor
-    }
-}
-
 /// Format of the test results output (see [`TestOpts::format`])
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub enum OutputFormat {
     /// Verbose output
+    #[default]
     Pretty,
+    /// Verbose output with the progress of the run redrawn in place, if a terminal
+    Progress,
     /// Quiet output
     Terse,
+    /// Results grouped by module path
+    Tree,
     /// JSON output
     Json,
 }
 
-impl Default for OutputFormat {
-    fn default() -> Self {
-        Self::Pretty
-    }
-}
-
 pub const UNSTABLE_OPTIONS: &str = "unstable-options";
 
 pub const OPTIONS_HELP: &str = r#"
@@ -94,6 +107,16 @@ Options:
         --skip FILTER   Skip tests whose names contain FILTER (this flag can
                         be used multiple times)
         --exact         Exactly match filters rather than by substring
+        --filter-expr EXPR
+                        Only run tests matching EXPR (this flag can be used
+                        multiple times). EXPR combines `name(GLOB)`,
+                        `module(PATH)`, `kind(unit|integration|doc|unknown)`,
+                        `source(GLOB)`, `ignored`, and `all` with `not`,
+                        `and`, `or`, and parentheses
+        --tag TAG       Only run tests tagged with TAG (this flag can be used
+                        multiple times)
+        --skip-tag TAG  Skip tests tagged with TAG (this flag can be used
+                        multiple times)
         --test          Run tests and not benchmarks
         --bench         Run benchmarks instead of tests
         --ignored       Run only ignored tests
@@ -105,17 +128,34 @@ Options:
         --show-output   Show captured stdout of successful tests
         --test-threads n_threads
                         Number of threads used for running tests in parallel
+        --timings PATH  Start the slowest tests first, according to durations
+                        recorded in PATH, and record this run's durations
+        --rerun-failed  Only run the tests that failed in the previous run
+        --report-time   Show how long each test took, and list the slowest
+                        tests
+        --seed SEED     Derive each test's random seed from SEED rather than a
+                        random one, to reproduce a previous run
+        --history PATH  Append each run's json events to PATH; with --list,
+                        report each test's pass rate, recent flips, and
+                        duration trend from PATH
         --color auto|always|never
                         Configure coloring of output:
                         auto = colorize if stdout is a tty and tests are run
                         on serially (default);
                         always = always colorize output;
                         never = never colorize output;
-        --format pretty|terse|json
+        --format pretty|progress|terse|tree|json
                         Configure formatting of output:
                         pretty = Print verbose output;
+                        progress = Print verbose output along with the
+                        tests still running, if stdout is a terminal;
                         terse = Display one character per test;
+                        tree = Group results by module path, collapsing
+                        groups that fully passed;
                         json = Output a json document;
+        --json-logfile PATH
+                        Also write json events to PATH, independent of
+                        --format
         --list          List all tests and benchmarks
     -q, --quiet         Display one character per test instead of one line.
                         Alias to --format=terse
@@ -210,6 +250,65 @@ impl TestOptsBuilder {
                     .within(arg)?;
                 self.opts.skip.push(filter.to_owned());
             }
+            Long("filter-expr") => {
+                let expr = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("EXPR")))
+                    .parse()
+                    .within(arg)?;
+                self.opts.filter_exprs.push(expr);
+            }
+            Long("tag") => {
+                let tag = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("TAG")))
+                    .string("TAG")
+                    .within(arg)?;
+                self.opts.tags.push(tag.to_owned());
+            }
+            Long("skip-tag") => {
+                let tag = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("TAG")))
+                    .string("TAG")
+                    .within(arg)?;
+                self.opts.skip_tags.push(tag.to_owned());
+            }
+            Long("timings") => {
+                let path = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("PATH")))
+                    .within(arg)?;
+                self.opts.timings = Some(path.into());
+            }
+            Long("seed") => {
+                let seed = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("SEED")))
+                    .parse()
+                    .within(arg)?;
+                self.opts.seed = Some(seed);
+            }
+            Long("json-logfile") => {
+                let path = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("PATH")))
+                    .within(arg)?;
+                self.opts.json_logfile = Some(path.into());
+            }
+            Long("history") => {
+                let path = parser
+                    .next_flag_value()
+                    .ok_or_missing(Value(std::ffi::OsStr::new("PATH")))
+                    .within(arg)?;
+                self.opts.history = Some(path.into());
+            }
+            Long("rerun-failed") => {
+                self.opts.rerun_failed = true;
+            }
+            Long("report-time") => {
+                self.opts.report_time = true;
+            }
             Long("exact") => {
                 self.opts.filter_exact = true;
             }
@@ -238,11 +337,13 @@ impl TestOptsBuilder {
                 let format = parser
                     .next_flag_value()
                     .ok_or_missing(Value(std::ffi::OsStr::new("FORMAT")))
-                    .one_of(&["pretty", "terse", "json"])
+                    .one_of(&["pretty", "progress", "terse", "tree", "json"])
                     .within(arg)?;
                 self.format = Some(match format {
                     "pretty" => OutputFormat::Pretty,
+                    "progress" => OutputFormat::Progress,
                     "terse" => OutputFormat::Terse,
+                    "tree" => OutputFormat::Tree,
                     "json" => OutputFormat::Json,
                     _ => unreachable!("`one_of` should prevent this"),
                 });
@@ -284,6 +385,41 @@ impl TestOptsBuilder {
         if self.format.is_some() && !allow_unstable_options {
             return Err(LexError::msg("`--format` requires `-Zunstable-options`"));
         }
+        if !self.opts.filter_exprs.is_empty() && !allow_unstable_options {
+            return Err(LexError::msg(
+                "`--filter-expr` requires `-Zunstable-options`",
+            ));
+        }
+        if !self.opts.tags.is_empty() && !allow_unstable_options {
+            return Err(LexError::msg("`--tag` requires `-Zunstable-options`"));
+        }
+        if !self.opts.skip_tags.is_empty() && !allow_unstable_options {
+            return Err(LexError::msg("`--skip-tag` requires `-Zunstable-options`"));
+        }
+        if self.opts.json_logfile.is_some() && !allow_unstable_options {
+            return Err(LexError::msg(
+                "`--json-logfile` requires `-Zunstable-options`",
+            ));
+        }
+        if self.opts.timings.is_some() && !allow_unstable_options {
+            return Err(LexError::msg("`--timings` requires `-Zunstable-options`"));
+        }
+        if self.opts.seed.is_some() && !allow_unstable_options {
+            return Err(LexError::msg("`--seed` requires `-Zunstable-options`"));
+        }
+        if self.opts.history.is_some() && !allow_unstable_options {
+            return Err(LexError::msg("`--history` requires `-Zunstable-options`"));
+        }
+        if self.opts.report_time && !allow_unstable_options {
+            return Err(LexError::msg(
+                "`--report-time` requires `-Zunstable-options`",
+            ));
+        }
+        if self.opts.rerun_failed && !allow_unstable_options {
+            return Err(LexError::msg(
+                "`--rerun-failed` requires `-Zunstable-options`",
+            ));
+        }
         if let Some(format) = self.format {
             self.opts.format = format;
         } else if self.quiet {
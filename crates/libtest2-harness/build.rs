@@ -0,0 +1,6 @@
+fn main() {
+    // Only available to build scripts, forward it for reporting the run's environment
+    let target = std::env::var("TARGET").unwrap();
+    println!("cargo:rustc-env=LIBTEST2_TARGET={target}");
+    println!("cargo:rerun-if-changed=build.rs");
+}
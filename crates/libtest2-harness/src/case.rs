@@ -1,6 +1,10 @@
 pub(crate) use crate::*;
 
-pub trait Case: Send + Sync + 'static {
+/// A test to run
+///
+/// Cases are `Send` and `Sync` so they can run on worker threads, unless registered with
+/// [`Harness::main_thread_case`].
+pub trait Case: 'static {
     /// The name of a test
     ///
     /// By convention this follows the rules for rust paths; i.e., it should be a series of
@@ -11,13 +15,27 @@ pub trait Case: Send + Sync + 'static {
     fn source(&self) -> Option<&Source>;
     /// This case cannot run in parallel to other cases within this binary
     fn exclusive(&self, state: &TestContext) -> bool;
+    /// This case only runs with `--ignored` or `--include-ignored`
+    ///
+    /// Cases can also decide while running, see [`TestContext::ignore`].
+    fn ignored(&self) -> bool {
+        false
+    }
+    /// Parameters this case was generated from, as `(name, value)` pairs
+    fn params(&self) -> &[(String, String)] {
+        &[]
+    }
+    /// Categories this case belongs to, e.g. `slow`, for selecting with `--tag` and `--skip-tag`
+    fn tags(&self) -> &[String] {
+        &[]
+    }
 
     fn run(&self, state: &TestContext) -> Result<(), RunError>;
 }
 
 /// Type of the test according to the [rust book](https://doc.rust-lang.org/cargo/guide/tests.html)
 /// conventions.
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq, Hash)]
 pub enum TestKind {
     /// Unit-tests are expected to be in the `src` folder of the crate.
     UnitTest,
@@ -27,15 +45,10 @@ pub enum TestKind {
     DocTest,
     /// Tests for the sources that don't follow the project layout convention
     /// (e.g. tests in raw `main.rs` compiled by calling `rustc --test` directly).
+    #[default]
     Unknown,
 }
 
-impl Default for TestKind {
-    fn default() -> Self {
-        Self::Unknown
-    }
-}
-
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum Source {
@@ -55,13 +68,19 @@ pub type RunResult = Result<(), RunError>;
 pub struct RunError {
     status: notify::MessageKind,
     cause: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    location: Option<String>,
+    expected: Option<String>,
+    actual: Option<String>,
+    left: Option<String>,
+    right: Option<String>,
+    backtrace: Option<String>,
 }
 
 impl RunError {
     pub fn with_cause(cause: impl std::error::Error + Send + Sync + 'static) -> Self {
         Self {
-            status: notify::MessageKind::Error,
             cause: Some(Box::new(cause)),
+            ..Self::new(notify::MessageKind::Error)
         }
     }
 
@@ -69,26 +88,100 @@ impl RunError {
         Self::with_cause(Message(cause.to_string()))
     }
 
-    pub(crate) fn ignore() -> Self {
+    /// Where the failure happened, e.g. [`std::panic::Location::caller`]
+    pub fn with_location(mut self, location: &std::panic::Location<'_>) -> Self {
+        self.location = Some(location.to_string());
+        self
+    }
+
+    /// The value the case expected and what it got instead
+    ///
+    /// Multi-line values are reported as a line diff.
+    pub fn with_expected_actual(
+        mut self,
+        expected: impl std::fmt::Display,
+        actual: impl std::fmt::Display,
+    ) -> Self {
+        self.expected = Some(expected.to_string());
+        self.actual = Some(actual.to_string());
+        self
+    }
+
+    /// The sides of a failed comparison that doesn't say which side was expected, e.g.
+    /// `assert_eq!`
+    pub(crate) fn with_left_right(mut self, left: &str, right: &str) -> Self {
+        self.left = Some(left.to_owned());
+        self.right = Some(right.to_owned());
+        self
+    }
+
+    pub(crate) fn with_panic_details(
+        mut self,
+        location: Option<String>,
+        backtrace: Option<String>,
+    ) -> Self {
+        self.location = location;
+        self.backtrace = backtrace;
+        self
+    }
+
+    fn new(status: notify::MessageKind) -> Self {
         Self {
-            status: notify::MessageKind::Ignored,
+            status,
             cause: None,
+            location: None,
+            expected: None,
+            actual: None,
+            left: None,
+            right: None,
+            backtrace: None,
         }
     }
 
+    pub(crate) fn ignore() -> Self {
+        Self::new(notify::MessageKind::Ignored)
+    }
+
     pub(crate) fn ignore_for(reason: String) -> Self {
         Self {
-            status: notify::MessageKind::Ignored,
             cause: Some(Box::new(Message(reason))),
+            ..Self::new(notify::MessageKind::Ignored)
         }
     }
 
+    pub(crate) fn cancelled() -> Self {
+        Self::new(notify::MessageKind::Cancelled)
+    }
+
     pub(crate) fn status(&self) -> notify::MessageKind {
         self.status
     }
 
-    pub(crate) fn cause(&self) -> Option<&(dyn std::error::Error + Send + Sync)> {
-        self.cause.as_ref().map(|b| b.as_ref())
+    /// Report this error for the case `name`
+    pub(crate) fn to_event(
+        &self,
+        name: String,
+        elapsed_s: Option<notify::Elapsed>,
+    ) -> notify::event::CaseMessage {
+        let mut causes = Vec::new();
+        let mut source = self.cause.as_ref().and_then(|c| c.source());
+        while let Some(cause) = source {
+            causes.push(cause.to_string());
+            source = cause.source();
+        }
+        notify::event::CaseMessage {
+            name,
+            kind: self.status,
+            message: self.cause.as_ref().map(|c| c.to_string()),
+            location: self.location.clone(),
+            causes,
+            expected: self.expected.clone(),
+            actual: self.actual.clone(),
+            left: self.left.clone(),
+            right: self.right.clone(),
+            backtrace: self.backtrace.clone(),
+            elapsed_s,
+        }
     }
 }
 
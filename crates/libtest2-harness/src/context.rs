@@ -4,6 +4,14 @@ pub(crate) use crate::*;
 pub struct TestContext {
     mode: RunMode,
     run_ignored: bool,
+    scratch_root: std::path::PathBuf,
+    executor: executor::Executor,
+    cancellation: std::sync::Arc<Cancellation>,
+    interrupt_grace: std::time::Duration,
+    run_seed: u64,
+    start: std::time::Instant,
+    case: Option<CaseState>,
+    errors: std::sync::Mutex<Vec<RunError>>,
 }
 
 impl TestContext {
@@ -26,6 +34,131 @@ impl TestContext {
     pub fn current_mode(&self) -> RunMode {
         self.mode
     }
+
+    /// Report a failure without stopping the case
+    ///
+    /// Each recorded error is reported separately and the case fails once it completes.
+    pub fn record_error(&self, error: impl Into<RunError>) {
+        self.errors
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(error.into());
+    }
+
+    /// Whether the run is being torn down, e.g. by `--fail-fast` or Ctrl-C
+    ///
+    /// Long-running cases should check this periodically, see [`TestContext::check_cancelled`].
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancellation.lock()
+    }
+
+    /// Stop the case if the run is being torn down
+    ///
+    /// The case is reported as cancelled rather than as failed.
+    pub fn check_cancelled(&self) -> Result<(), RunError> {
+        if self.is_cancelled() {
+            Err(RunError::cancelled())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Block until the run is torn down or `timeout` elapses
+    ///
+    /// Returns whether the run is being torn down.
+    pub fn wait_for_cancellation(&self, timeout: std::time::Duration) -> bool {
+        let deadline = std::time::Instant::now() + timeout;
+        let mut cancelled = self.cancellation.lock();
+        while !*cancelled {
+            let Some(remaining) = deadline.checked_duration_since(std::time::Instant::now()) else {
+                break;
+            };
+            cancelled = self
+                .cancellation
+                .condvar
+                .wait_timeout(cancelled, remaining)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+        *cancelled
+    }
+
+    /// Run `future` to completion with the executor from [`Harness::executor`]
+    pub fn block_on(&self, future: impl std::future::Future<Output = RunResult>) -> RunResult {
+        self.executor.block_on(Box::pin(future))
+    }
+
+    /// Run `run` as a sub-case named `<current case>::<name>`
+    ///
+    /// The sub-case is reported like a case found during discovery, but counted separately.
+    /// If it fails, the current case fails as well.
+    ///
+    /// Returns whether the sub-case succeeded.
+    pub fn subcase(&self, name: &str, run: impl FnOnce(&TestContext) -> RunResult) -> bool {
+        let case = self
+            .case
+            .as_ref()
+            .expect("sub-cases can only be created while running a case");
+        let name = format!("{}::{name}", case.name);
+        case.push_event(
+            notify::event::CaseStart {
+                name: name.clone(),
+                parent: Some(case.name.clone()),
+                elapsed_s: Some(self.elapsed()),
+            }
+            .into(),
+        );
+
+        let context = self.for_case_with_events(&name, case.events.clone());
+        let outcome = catch_panic(|| run(&context));
+        let mut errors = context.take_errors();
+        errors.extend(outcome.err());
+        let (success, events) = context.complete(errors);
+        for event in events {
+            case.push_event(event);
+        }
+
+        if !success {
+            self.record_error(RunError::fail(format_args!("sub-case `{name}` failed")));
+        }
+        success
+    }
+
+    /// Seed for randomizing the current case
+    ///
+    /// Derived from the run's seed and the case's name, so passing the run's seed back with
+    /// `--seed` reproduces the same randomness.  The run's seed is reported at the start of a
+    /// run and when it fails.
+    pub fn seed(&self) -> u64 {
+        match &self.case {
+            Some(case) => case.seed,
+            None => self.run_seed,
+        }
+    }
+
+    /// Scratch directory, named after the current case
+    ///
+    /// The directory is created empty on first access.  It is deleted if the case succeeds and
+    /// retained for debugging if it fails.
+    pub fn scratch_dir(&self) -> std::io::Result<&std::path::Path> {
+        let Some(case) = self.case.as_ref() else {
+            return Err(std::io::Error::other(
+                "scratch directories are only available while running a case",
+            ));
+        };
+        let scratch = &case.scratch;
+        let mut created = scratch.created.lock().unwrap_or_else(|e| e.into_inner());
+        if !*created {
+            match std::fs::remove_dir_all(&scratch.path) {
+                Ok(()) => {}
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err),
+            }
+            std::fs::create_dir_all(&scratch.path)?;
+            *created = true;
+        }
+        Ok(&scratch.path)
+    }
 }
 
 impl TestContext {
@@ -33,6 +166,14 @@ impl TestContext {
         Self {
             mode: Default::default(),
             run_ignored: false,
+            scratch_root: default_scratch_root(),
+            executor: Default::default(),
+            cancellation: Default::default(),
+            interrupt_grace: std::time::Duration::from_secs(5),
+            run_seed: 0,
+            start: std::time::Instant::now(),
+            case: None,
+            errors: Default::default(),
         }
     }
 
@@ -43,4 +184,273 @@ impl TestContext {
     pub(crate) fn set_run_ignored(&mut self, yes: bool) {
         self.run_ignored = yes;
     }
+
+    pub(crate) fn set_executor(&mut self, executor: executor::Executor) {
+        self.executor = executor;
+    }
+
+    /// Signal running cases that the run is being torn down
+    pub(crate) fn cancel(&self) {
+        self.cancellation.cancel();
+    }
+
+    /// Whether the run was interrupted, see [`Canceller::interrupt`]
+    pub(crate) fn is_interrupted(&self) -> bool {
+        self.cancellation
+            .interrupted
+            .load(std::sync::atomic::Ordering::SeqCst)
+    }
+
+    pub(crate) fn set_interrupt_grace(&mut self, grace: std::time::Duration) {
+        self.interrupt_grace = grace;
+    }
+
+    pub(crate) fn interrupt_grace(&self) -> std::time::Duration {
+        self.interrupt_grace
+    }
+
+    #[cfg(all(feature = "signals", any(unix, windows)))]
+    pub(crate) fn canceller(&self) -> Canceller {
+        Canceller(self.cancellation.clone())
+    }
+
+    pub(crate) fn set_run_seed(&mut self, seed: u64) {
+        self.run_seed = seed;
+    }
+
+    pub(crate) fn set_start(&mut self, start: std::time::Instant) {
+        self.start = start;
+    }
+
+    pub(crate) fn set_scratch_root(&mut self, root: std::path::PathBuf) {
+        self.scratch_root = root;
+    }
+
+    /// Where the names of failed cases are recorded for `--rerun-failed`
+    pub(crate) fn failures_path(&self) -> std::path::PathBuf {
+        self.scratch_root.join("failed.txt")
+    }
+
+    /// Create the context for running `name`
+    pub(crate) fn for_case(&self, name: &str) -> Self {
+        self.for_case_with_events(name, Default::default())
+    }
+
+    fn for_case_with_events(
+        &self,
+        name: &str,
+        events: std::sync::Arc<std::sync::Mutex<Vec<notify::Event>>>,
+    ) -> Self {
+        let path = self.scratch_root.join(scratch_dir_name(name));
+        Self {
+            mode: self.mode,
+            run_ignored: self.run_ignored,
+            scratch_root: self.scratch_root.clone(),
+            executor: self.executor.clone(),
+            cancellation: self.cancellation.clone(),
+            interrupt_grace: self.interrupt_grace,
+            run_seed: self.run_seed,
+            start: self.start,
+            case: Some(CaseState {
+                name: name.to_owned(),
+                seed: case_seed(self.run_seed, name),
+                scratch: Scratch {
+                    path,
+                    created: Default::default(),
+                },
+                events,
+            }),
+            errors: Default::default(),
+        }
+    }
+
+    pub(crate) fn take_errors(&self) -> Vec<RunError> {
+        std::mem::take(&mut *self.errors.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Events reported by sub-cases since the last call
+    pub(crate) fn take_events(&self) -> Vec<notify::Event> {
+        let Some(case) = self.case.as_ref() else {
+            return Vec::new();
+        };
+        std::mem::take(&mut *case.events.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Report the outcome of the current case
+    ///
+    /// Returns whether the case succeeded, along with the events to report.
+    pub(crate) fn complete(&self, errors: Vec<RunError>) -> (bool, Vec<notify::Event>) {
+        let case = self
+            .case
+            .as_ref()
+            .expect("only called while running a case");
+
+        let mut events = Vec::new();
+        let mut case_status = None;
+        for err in errors {
+            case_status = case_status.max(Some(err.status()));
+            events.push(err.to_event(case.name.clone(), Some(self.elapsed())).into());
+        }
+
+        let success = case_status != Some(notify::MessageKind::Error);
+        let scratch_dir = self
+            .finish_scratch(success)
+            .map(|p| p.display().to_string());
+
+        events.push(
+            notify::event::CaseComplete {
+                name: case.name.clone(),
+                scratch_dir,
+                elapsed_s: Some(self.elapsed()),
+            }
+            .into(),
+        );
+
+        (success, events)
+    }
+
+    fn elapsed(&self) -> notify::Elapsed {
+        notify::Elapsed(self.start.elapsed())
+    }
+
+    /// Clean up the scratch directory, returning it if it was retained
+    fn finish_scratch(&self, success: bool) -> Option<&std::path::Path> {
+        let scratch = &self.case.as_ref()?.scratch;
+        let created = *scratch.created.lock().unwrap_or_else(|e| e.into_inner());
+        if !created {
+            return None;
+        }
+        if success {
+            // Nothing to report if this fails, the case itself passed
+            let _ = std::fs::remove_dir_all(&scratch.path);
+            None
+        } else {
+            Some(&scratch.path)
+        }
+    }
+}
+
+/// Mix `name` into `run_seed`
+///
+/// This must stay stable across platforms and releases for seeds to be reproducible.
+fn case_seed(run_seed: u64, name: &str) -> u64 {
+    // splitmix64, so similar names and seeds still end up far apart
+    let mut seed = run_seed ^ fnv1a(name);
+    seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    seed = (seed ^ (seed >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    seed = (seed ^ (seed >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    seed ^ (seed >> 31)
+}
+
+/// FNV-1a
+fn fnv1a(name: &str) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    for byte in name.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+fn default_scratch_root() -> std::path::PathBuf {
+    let bin = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.file_stem().map(|s| s.to_owned()))
+        .unwrap_or_else(|| "test".into());
+    std::env::temp_dir().join("libtest2").join(bin)
+}
+
+#[derive(Debug)]
+struct CaseState {
+    name: String,
+    seed: u64,
+    scratch: Scratch,
+    /// Events from sub-cases, shared with them so nested sub-cases are reported in order
+    events: std::sync::Arc<std::sync::Mutex<Vec<notify::Event>>>,
+}
+
+impl CaseState {
+    fn push_event(&self, event: notify::Event) {
+        self.events
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(event);
+    }
+}
+
+/// Tear down a run from outside of it, e.g. from a signal handler
+#[cfg(all(feature = "signals", any(unix, windows)))]
+#[derive(Clone, Debug)]
+pub(crate) struct Canceller(std::sync::Arc<Cancellation>);
+
+#[cfg(all(feature = "signals", any(unix, windows)))]
+impl Canceller {
+    /// Stop the run, cancelling running cases
+    ///
+    /// Returns whether this is the first interrupt.
+    pub(crate) fn interrupt(&self) -> bool {
+        let first = !self
+            .0
+            .interrupted
+            .swap(true, std::sync::atomic::Ordering::SeqCst);
+        self.0.cancel();
+        first
+    }
+}
+
+#[derive(Default, Debug)]
+struct Cancellation {
+    cancelled: std::sync::Mutex<bool>,
+    condvar: std::sync::Condvar,
+    interrupted: std::sync::atomic::AtomicBool,
+}
+
+impl Cancellation {
+    fn lock(&self) -> std::sync::MutexGuard<'_, bool> {
+        self.cancelled.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn cancel(&self) {
+        *self.lock() = true;
+        self.condvar.notify_all();
+    }
+}
+
+#[derive(Debug)]
+struct Scratch {
+    path: std::path::PathBuf,
+    created: std::sync::Mutex<bool>,
+}
+
+/// Name of the scratch directory for the case `name`
+///
+/// Each case gets a directory directly under the root, so clearing one case's directory never
+/// touches another's, e.g. `a` and `a::b`.  Sanitizing can map different names to the same
+/// string, so the name's hash keeps them apart.
+fn scratch_dir_name(name: &str) -> String {
+    // Leave room for the hash within common file name limits
+    const MAX_READABLE: usize = 64;
+
+    let mut readable = sanitize_path_component(name);
+    // Only ASCII remains after sanitizing
+    readable.truncate(MAX_READABLE);
+    format!("{readable}-{:016x}", fnv1a(name))
+}
+
+fn sanitize_path_component(component: &str) -> String {
+    let component = component
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if component.is_empty() || component.chars().all(|c| c == '.') {
+        "_".to_owned()
+    } else {
+        component
+    }
 }
@@ -0,0 +1,59 @@
+use crate::RunResult;
+
+/// A type-erased future for a case, see [`crate::Harness::executor`]
+pub type LocalBoxFuture<'a> = std::pin::Pin<Box<dyn std::future::Future<Output = RunResult> + 'a>>;
+
+#[derive(Clone)]
+pub(crate) struct Executor(std::sync::Arc<dyn Fn(LocalBoxFuture<'_>) -> RunResult + Send + Sync>);
+
+impl Executor {
+    pub(crate) fn new(
+        block_on: impl Fn(LocalBoxFuture<'_>) -> RunResult + Send + Sync + 'static,
+    ) -> Self {
+        Self(std::sync::Arc::new(block_on))
+    }
+
+    pub(crate) fn block_on(&self, future: LocalBoxFuture<'_>) -> RunResult {
+        (self.0)(future)
+    }
+}
+
+impl Default for Executor {
+    fn default() -> Self {
+        Self::new(block_on)
+    }
+}
+
+impl std::fmt::Debug for Executor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Executor").finish_non_exhaustive()
+    }
+}
+
+/// Poll `future` to completion on the current thread
+///
+/// This makes no assumptions about the runtime, parking the thread until the future is woken.
+fn block_on(future: LocalBoxFuture<'_>) -> RunResult {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: std::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+
+        fn wake_by_ref(self: &std::sync::Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let mut future = future;
+    let waker = std::task::Waker::from(std::sync::Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = std::task::Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            std::task::Poll::Ready(outcome) => return outcome,
+            // Spurious wake ups are fine, we'll just poll again
+            std::task::Poll::Pending => std::thread::park(),
+        }
+    }
+}
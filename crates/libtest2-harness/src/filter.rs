@@ -0,0 +1,92 @@
+use libtest_lexarg::FilterExpr;
+
+pub(crate) use crate::*;
+
+/// Whether `case` is selected by `expr`
+pub(crate) fn matches(expr: &FilterExpr, case: &dyn Case) -> bool {
+    match expr {
+        FilterExpr::All => true,
+        FilterExpr::Name(glob) => glob_matches(glob, case.name()),
+        FilterExpr::Module(path) => {
+            let path = path.trim_end_matches("::");
+            case.name()
+                .strip_prefix(path)
+                .is_some_and(|rest| rest.starts_with("::"))
+        }
+        FilterExpr::Kind(kind) => kind_str(case.kind()) == kind,
+        FilterExpr::Source(glob) => {
+            let path = match case.source() {
+                Some(Source::Rust { source_file, .. }) => source_file,
+                Some(Source::Path(path)) => path,
+                None => return false,
+            };
+            // Match the same glob on every platform
+            let path = path.to_string_lossy().replace('\\', "/");
+            glob_matches(glob, &path)
+        }
+        FilterExpr::Ignored => case.ignored(),
+        FilterExpr::Not(expr) => !matches(expr, case),
+        FilterExpr::And(lhs, rhs) => matches(lhs, case) && matches(rhs, case),
+        FilterExpr::Or(lhs, rhs) => matches(lhs, case) || matches(rhs, case),
+    }
+}
+
+fn kind_str(kind: TestKind) -> &'static str {
+    match kind {
+        TestKind::UnitTest => "unit",
+        TestKind::IntegrationTest => "integration",
+        TestKind::DocTest => "doc",
+        TestKind::Unknown => "unknown",
+    }
+}
+
+/// Whether all of `text` matches `glob`, where `*` matches any run of characters and `?` any one
+fn glob_matches(glob: &str, text: &str) -> bool {
+    let glob = glob.chars().collect::<Vec<_>>();
+    let text = text.chars().collect::<Vec<_>>();
+    let (mut g, mut t) = (0, 0);
+    // Where to resume if what follows the last `*` stops matching
+    let mut backtrack = None;
+    while t < text.len() {
+        match glob.get(g) {
+            Some('*') => {
+                backtrack = Some((g, t));
+                g += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                g += 1;
+                t += 1;
+            }
+            _ => {
+                // Let the last `*` match one more character
+                let Some((star, star_t)) = backtrack else {
+                    return false;
+                };
+                backtrack = Some((star, star_t + 1));
+                g = star + 1;
+                t = star_t + 1;
+            }
+        }
+    }
+    glob[g..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        assert!(glob_matches("foo", "foo"));
+        assert!(!glob_matches("foo", "foobar"));
+        assert!(glob_matches("foo*", "foobar"));
+        assert!(glob_matches("*bar", "foobar"));
+        assert!(glob_matches("*::slow_*", "parser::expr::slow_deep"));
+        assert!(!glob_matches("*::slow_*", "slow_deep"));
+        assert!(glob_matches("f?o*r", "foobar"));
+        assert!(glob_matches("*a*a*", "banana"));
+        assert!(!glob_matches("*a*a*b", "banana"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("?", ""));
+    }
+}
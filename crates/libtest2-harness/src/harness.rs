@@ -1,36 +1,132 @@
 use libtest_lexarg::OutputFormat;
 
-use crate::{cli, notify, Case, RunError, RunMode, TestContext};
+use crate::{cli, executor, notify, Case, RunError, RunMode, RunResult, TestContext};
 
 pub struct Harness {
     raw: std::io::Result<Vec<std::ffi::OsString>>,
-    cases: Vec<Box<dyn Case>>,
+    cases: Vec<RegisteredCase>,
+    hooks: Hooks,
+    context: TestContext,
 }
 
 impl Harness {
     pub fn with_args(args: impl IntoIterator<Item = impl Into<std::ffi::OsString>>) -> Self {
         let raw = expand_args(args);
-        Self { raw, cases: vec![] }
+        Self {
+            raw,
+            cases: vec![],
+            hooks: Default::default(),
+            context: TestContext::new(),
+        }
     }
 
     pub fn with_env() -> Self {
         let raw = std::env::args_os();
         let raw = expand_args(raw);
-        Self { raw, cases: vec![] }
+        Self {
+            raw,
+            cases: vec![],
+            hooks: Default::default(),
+            context: TestContext::new(),
+        }
     }
 
-    pub fn case(mut self, case: impl Case + 'static) -> Self {
-        self.cases.push(Box::new(case));
+    pub fn case(mut self, case: impl Case + Send + Sync) -> Self {
+        self.cases.push(RegisteredCase::Shared(Box::new(case)));
         self
     }
 
-    pub fn cases(mut self, cases: impl IntoIterator<Item = impl Case + 'static>) -> Self {
+    pub fn cases(mut self, cases: impl IntoIterator<Item = impl Case + Send + Sync>) -> Self {
         for case in cases {
-            self.cases.push(Box::new(case));
+            self.cases.push(RegisteredCase::Shared(Box::new(case)));
         }
         self
     }
 
+    /// Register a case that must run on the main thread
+    ///
+    /// Unlike [`Harness::case`], the case need not be `Send` or `Sync`, e.g. for GUI toolkits or
+    /// FFI libraries that may only be used from the main thread.
+    /// These cases run on the main thread while other cases run on worker threads.
+    pub fn main_thread_case(mut self, case: impl Case) -> Self {
+        self.cases.push(RegisteredCase::MainThread(Box::new(case)));
+        self
+    }
+
+    /// Register cases that must run on the main thread, see [`Harness::main_thread_case`]
+    pub fn main_thread_cases(mut self, cases: impl IntoIterator<Item = impl Case>) -> Self {
+        for case in cases {
+            self.cases.push(RegisteredCase::MainThread(Box::new(case)));
+        }
+        self
+    }
+
+    /// Run `hook` before each case, in registration order
+    ///
+    /// If a hook fails, the case is reported with the hook's error and is not run.
+    pub fn before_each(
+        mut self,
+        hook: impl Fn(&str, &TestContext) -> RunResult + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.before.push(Box::new(hook));
+        self
+    }
+
+    /// Run `hook` after each case, in registration order
+    ///
+    /// These run even if the case or a [`Harness::before_each`] hook failed or ignored the case,
+    /// but not for cases marked as ignored up front.
+    /// Any failures are reported in addition to the case's own, and fail ignored cases.
+    pub fn after_each(
+        mut self,
+        hook: impl Fn(&str, &TestContext) -> RunResult + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.after.push(Box::new(hook));
+        self
+    }
+
+    /// Directory under which [`TestContext::scratch_dir`] creates per-case directories
+    ///
+    /// Defaults to a directory named after the test binary within [`std::env::temp_dir`].
+    /// The names of failed cases, if any, are also recorded here for `--rerun-failed`.
+    pub fn scratch_root(mut self, root: impl Into<std::path::PathBuf>) -> Self {
+        self.context.set_scratch_root(root.into());
+        self
+    }
+
+    /// Runtime for polling futures passed to [`TestContext::block_on`]
+    ///
+    /// By default, futures are polled on the case's thread, parking it until woken.
+    /// To use another runtime, `block_on` should run the future to completion, e.g. with `tokio`:
+    /// ```ignore
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// libtest2_harness::Harness::with_env()
+    ///     .executor(move |future| runtime.block_on(future))
+    ///     .main();
+    /// ```
+    pub fn executor(
+        mut self,
+        block_on: impl Fn(executor::LocalBoxFuture<'_>) -> RunResult + Send + Sync + 'static,
+    ) -> Self {
+        self.context.set_executor(executor::Executor::new(block_on));
+        self
+    }
+
+    /// How long running cases may take to finish after Ctrl-C
+    ///
+    /// Ctrl-C is only handled with the `signals` feature, which is off by default to keep the
+    /// `ctrlc` dependency out of builds that don't need it.
+    /// Cases can notice the interrupt through [`TestContext::is_cancelled`].
+    /// Any still running afterwards are reported as interrupted,
+    /// except for [main thread cases][Harness::main_thread_case] which are always waited on.
+    /// Cases run one at a time are moved off the main thread to enforce this,
+    /// unless the `threads` feature is off.
+    /// Interrupting a second time exits immediately.
+    pub fn interrupt_grace_period(mut self, grace: std::time::Duration) -> Self {
+        self.context.set_interrupt_grace(grace);
+        self
+    }
+
     pub fn main(mut self) -> ! {
         let start = std::time::Instant::now();
 
@@ -55,17 +151,65 @@ impl Harness {
         }
         .write_global();
 
+        let timings = match &opts.timings {
+            Some(path) => notify::Timings::load(path).unwrap_or_else(|err| {
+                eprintln!("{err}");
+                std::process::exit(1)
+            }),
+            None => Default::default(),
+        };
+        let failures_path = self.context.failures_path();
+        let rerun = if opts.rerun_failed {
+            let failures = notify::Failures::load(&failures_path).unwrap_or_else(|err| {
+                eprintln!("{err}");
+                std::process::exit(1)
+            });
+            Some(failures)
+        } else {
+            None
+        };
         let mut notifier = notifier(&opts).unwrap_or_else(|err| {
             eprintln!("{err}");
             std::process::exit(1)
         });
-        discover(&start, &opts, &mut self.cases, notifier.as_mut()).unwrap_or_else(|err| {
+        notifier = Box::new(notify::FailuresNotifier::new(notifier, failures_path));
+        #[cfg(feature = "json")]
+        if let Some(path) = &opts.history {
+            notifier = Box::new(notify::HistoryNotifier::new(notifier, path.clone()));
+        }
+        if let Some(path) = &opts.timings {
+            notifier = Box::new(notify::TimingsNotifier::new(
+                notifier,
+                path.clone(),
+                timings.clone(),
+            ));
+        }
+        discover(
+            &start,
+            &raw,
+            &opts,
+            rerun.as_ref(),
+            &mut self.cases,
+            notifier.as_mut(),
+        )
+        .unwrap_or_else(|err| {
             eprintln!("{err}");
             std::process::exit(1)
         });
 
         if !opts.list {
-            match run(&start, &opts, self.cases, notifier.as_mut()) {
+            #[cfg(all(feature = "signals", any(unix, windows)))]
+            install_interrupt_handler(self.context.canceller());
+
+            match run(
+                &start,
+                &opts,
+                self.cases,
+                self.hooks,
+                self.context,
+                &timings,
+                notifier.as_mut(),
+            ) {
                 Ok(true) => {}
                 Ok(false) => std::process::exit(ERROR_EXIT_CODE),
                 Err(e) => {
@@ -81,6 +225,40 @@ impl Harness {
 
 const ERROR_EXIT_CODE: i32 = 101;
 
+/// Wind down the run on Ctrl-C or `SIGTERM`, see [`Harness::interrupt_grace_period`]
+#[cfg(all(feature = "signals", any(unix, windows)))]
+fn install_interrupt_handler(canceller: crate::context::Canceller) {
+    // The test binary may have installed its own handler, leave it be
+    let _ = ctrlc::set_handler(move || {
+        if !canceller.interrupt() {
+            std::process::exit(ERROR_EXIT_CODE);
+        }
+    });
+}
+
+type Hook = dyn Fn(&str, &TestContext) -> RunResult + Send + Sync;
+
+/// A case along with where it may run
+enum RegisteredCase {
+    Shared(Box<dyn Case + Send + Sync>),
+    MainThread(Box<dyn Case>),
+}
+
+impl RegisteredCase {
+    fn as_case(&self) -> &dyn Case {
+        match self {
+            Self::Shared(case) => case.as_ref(),
+            Self::MainThread(case) => case.as_ref(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct Hooks {
+    before: Vec<Box<Hook>>,
+    after: Vec<Box<Hook>>,
+}
+
 fn parse<'p>(parser: &mut cli::Parser<'p>) -> Result<libtest_lexarg::TestOpts, cli::LexError<'p>> {
     let mut test_opts = libtest_lexarg::TestOptsBuilder::new();
 
@@ -165,6 +343,10 @@ fn notifier(opts: &libtest_lexarg::TestOpts) -> std::io::Result<Box<dyn notify::
     let stdout = anstream::stdout();
     #[cfg(not(feature = "color"))]
     let stdout = std::io::stdout();
+    #[cfg(not(feature = "json"))]
+    if opts.history.is_some() {
+        return Err(std::io::Error::other("`--history` is not supported"));
+    }
     let notifier: Box<dyn notify::Notifier> = match opts.format {
         #[cfg(feature = "json")]
         OutputFormat::Json => Box::new(notify::JsonNotifier::new(stdout)),
@@ -175,21 +357,92 @@ fn notifier(opts: &libtest_lexarg::TestOpts) -> std::io::Result<Box<dyn notify::
                 "`--format=json` is not supported",
             ));
         }
+        #[cfg(feature = "json")]
+        _ if opts.list => match &opts.history {
+            Some(path) => Box::new(notify::HistoryListNotifier::new(
+                stdout,
+                notify::History::load(path)?,
+            )),
+            None => Box::new(notify::TerseListNotifier::new(stdout)),
+        },
+        #[cfg(not(feature = "json"))]
         _ if opts.list => Box::new(notify::TerseListNotifier::new(stdout)),
-        OutputFormat::Pretty => Box::new(notify::PrettyRunNotifier::new(stdout)),
-        OutputFormat::Terse => Box::new(notify::TerseRunNotifier::new(stdout)),
+        OutputFormat::Pretty => {
+            Box::new(notify::PrettyRunNotifier::new(stdout).report_time(opts.report_time))
+        }
+        OutputFormat::Progress => {
+            use std::io::IsTerminal as _;
+
+            if std::io::stdout().is_terminal() {
+                Box::new(
+                    notify::ProgressRunNotifier::new(stdout, std::io::stdout())
+                        .report_time(opts.report_time),
+                )
+            } else {
+                Box::new(notify::PrettyRunNotifier::new(stdout).report_time(opts.report_time))
+            }
+        }
+        OutputFormat::Terse => {
+            Box::new(notify::TerseRunNotifier::new(stdout).report_time(opts.report_time))
+        }
+        OutputFormat::Tree => {
+            Box::new(notify::TreeRunNotifier::new(stdout).report_time(opts.report_time))
+        }
+    };
+    let Some(path) = &opts.json_logfile else {
+        return Ok(notifier);
     };
-    Ok(notifier)
+    #[cfg(feature = "json")]
+    {
+        let logfile = std::fs::File::create(path).map_err(|err| {
+            std::io::Error::new(
+                err.kind(),
+                format!("failed to create {}: {err}", path.display()),
+            )
+        })?;
+        // Write each event as it happens as the process exits without flushing
+        let logfile = notify::JsonNotifier::new(std::io::LineWriter::new(logfile));
+        Ok(Box::new(notify::TeeNotifier::new(
+            notifier,
+            Box::new(logfile),
+        )))
+    }
+    #[cfg(not(feature = "json"))]
+    {
+        let _ = path;
+        Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            "`--json-logfile` is not supported",
+        ))
+    }
 }
 
 fn discover(
     start: &std::time::Instant,
+    raw: &[std::ffi::OsString],
     opts: &libtest_lexarg::TestOpts,
-    cases: &mut Vec<Box<dyn Case>>,
+    rerun: Option<&notify::Failures>,
+    cases: &mut Vec<RegisteredCase>,
     notifier: &mut dyn notify::Notifier,
 ) -> std::io::Result<()> {
+    let started_at = std::time::SystemTime::now()
+        .checked_sub(start.elapsed())
+        .and_then(|started_at| started_at.duration_since(std::time::UNIX_EPOCH).ok());
     notifier.notify(
         notify::event::DiscoverStart {
+            started_at_s: started_at.map(notify::Timestamp),
+            binary: std::env::current_exe()
+                .ok()
+                .map(|path| path.display().to_string()),
+            args: raw
+                .iter()
+                .skip(1)
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            current_dir: std::env::current_dir()
+                .ok()
+                .map(|path| path.display().to_string()),
+            target: Some(env!("LIBTEST2_TARGET").to_owned()),
             elapsed_s: Some(notify::Elapsed(start.elapsed())),
         }
         .into(),
@@ -206,33 +459,51 @@ fn discover(
 
     // Do this first so it applies to both discover and running
     cases.sort_unstable_by_key(|case| {
-        let priority = if opts.filters.is_empty() {
+        let priority = if let Some(rerun) = rerun {
+            rerun.position(case.as_case().name())
+        } else if opts.filters.is_empty() {
             Some(0)
         } else {
             opts.filters
                 .iter()
-                .position(|filter| matches_filter(case.as_ref(), filter))
+                .position(|filter| matches_filter(case.as_case(), filter))
         };
-        let name = case.name().to_owned();
+        let name = case.as_case().name().to_owned();
         (priority, name)
     });
 
     let mut retain_cases = Vec::with_capacity(cases.len());
     for case in cases.iter() {
+        let case = case.as_case();
         let filtered_in = opts.filters.is_empty()
             || opts
                 .filters
                 .iter()
-                .any(|filter| matches_filter(case.as_ref(), filter));
+                .any(|filter| matches_filter(case, filter));
         let filtered_out =
-            !opts.skip.is_empty() && opts.skip.iter().any(|sf| matches_filter(case.as_ref(), sf));
-        let retain_case = filtered_in && !filtered_out;
+            !opts.skip.is_empty() && opts.skip.iter().any(|sf| matches_filter(case, sf));
+        let expr_in = opts.filter_exprs.is_empty()
+            || opts
+                .filter_exprs
+                .iter()
+                .any(|expr| crate::filter::matches(expr, case));
+        let rerun_in = match rerun {
+            Some(rerun) => rerun.position(case.name()).is_some(),
+            None => true,
+        };
+        let tagged_in =
+            opts.tags.is_empty() || case.tags().iter().any(|tag| opts.tags.contains(tag));
+        let tagged_out = case.tags().iter().any(|tag| opts.skip_tags.contains(tag));
+        let retain_case =
+            rerun_in && filtered_in && expr_in && tagged_in && !filtered_out && !tagged_out;
         retain_cases.push(retain_case);
         notifier.notify(
             notify::event::DiscoverCase {
                 name: case.name().to_owned(),
                 mode: RunMode::Test,
                 selected: retain_case,
+                params: case.params().iter().cloned().collect(),
+                tags: case.tags().to_vec(),
                 elapsed_s: Some(notify::Elapsed(start.elapsed())),
             }
             .into(),
@@ -254,15 +525,13 @@ fn discover(
 fn run(
     start: &std::time::Instant,
     opts: &libtest_lexarg::TestOpts,
-    cases: Vec<Box<dyn Case>>,
+    cases: Vec<RegisteredCase>,
+    hooks: Hooks,
+    mut context: TestContext,
+    timings: &notify::Timings,
     notifier: &mut dyn notify::Notifier,
 ) -> std::io::Result<bool> {
-    notifier.notify(
-        notify::event::RunStart {
-            elapsed_s: Some(notify::Elapsed(start.elapsed())),
-        }
-        .into(),
-    )?;
+    let seed = opts.seed.unwrap_or_else(random_seed);
 
     if opts.no_capture {
         todo!("`--no-capture` is not yet supported");
@@ -273,7 +542,6 @@ fn run(
 
     let threads = opts.test_threads.map(|t| t.get()).unwrap_or(1);
 
-    let mut context = TestContext::new();
     let run_ignored = match opts.run_ignored {
         libtest_lexarg::RunIgnored::Yes | libtest_lexarg::RunIgnored::Only => true,
         libtest_lexarg::RunIgnored::No => false,
@@ -290,160 +558,369 @@ fn run(
     };
     context.set_mode(mode);
     context.set_run_ignored(run_ignored);
+    context.set_run_seed(seed);
+    context.set_start(*start);
     let context = std::sync::Arc::new(context);
+    let hooks = std::sync::Arc::new(hooks);
 
     let mut success = true;
+    let should_stop = |success: bool| (!success && opts.fail_fast) || context.is_interrupted();
+    // Selected cases that never started because the run stopped early
+    let mut not_run = Vec::new();
 
-    let (exclusive_cases, concurrent_cases) = if threads == 1 || cases.len() == 1 {
-        (cases, vec![])
+    // Cases started on other threads that have yet to complete
+    let mut running = std::collections::HashSet::new();
+
+    let mut exclusive_cases = Vec::new();
+    let mut concurrent_cases = Vec::new();
+    let mut main_thread_cases = Vec::new();
+    if threads == 1 || cases.len() == 1 {
+        exclusive_cases = cases;
     } else {
-        cases
-            .into_iter()
-            .partition::<Vec<_>, _>(|c| c.exclusive(&context))
-    };
-    if !concurrent_cases.is_empty() {
-        notifier.threaded(true);
-        struct RunningTest {
-            join_handle: std::thread::JoinHandle<()>,
-        }
-
-        impl RunningTest {
-            fn join(
-                self,
-                start: &std::time::Instant,
-                event: &notify::event::CaseComplete,
-                notifier: &mut dyn notify::Notifier,
-            ) -> std::io::Result<()> {
-                if self.join_handle.join().is_err() {
-                    let kind = notify::MessageKind::Error;
-                    let message = Some("panicked after reporting success".to_owned());
-                    notifier.notify(
-                        notify::event::CaseMessage {
-                            name: event.name.clone(),
-                            kind,
-                            message,
-                            elapsed_s: Some(notify::Elapsed(start.elapsed())),
-                        }
-                        .into(),
-                    )?;
+        for case in cases {
+            if case.as_case().exclusive(&context) {
+                exclusive_cases.push(case);
+            } else {
+                match case {
+                    RegisteredCase::Shared(case) => concurrent_cases.push(case),
+                    RegisteredCase::MainThread(case) => main_thread_cases.push(case),
                 }
-                Ok(())
             }
         }
+    }
+    if opts.filters.is_empty() && !opts.rerun_failed {
+        // Start the slowest cases first to shorten the tail of the run, assuming cases without
+        // timings may be slow.  Filters and reruns give the user direct control of the order
+        // instead.
+        concurrent_cases.sort_by_key(|case| {
+            std::cmp::Reverse(timings.get(case.name()).unwrap_or(std::time::Duration::MAX))
+        });
+    }
+    // Reported once the workers are spawned, to count only those that were
+    let run_started = start.elapsed();
+    let run_start = |threads: usize| -> notify::Event {
+        notify::event::RunStart {
+            seed: Some(notify::Seed(seed)),
+            threads: Some(threads),
+            elapsed_s: Some(notify::Elapsed(run_started)),
+        }
+        .into()
+    };
 
-        // Use a deterministic hasher
-        type TestMap = std::collections::HashMap<
-            String,
-            RunningTest,
-            std::hash::BuildHasherDefault<std::collections::hash_map::DefaultHasher>,
-        >;
+    if !concurrent_cases.is_empty() || !main_thread_cases.is_empty() {
+        notifier.threaded(true);
 
         let sync_success = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(success));
-        let mut running_tests: TestMap = Default::default();
-        let mut pending = 0;
+        let queue = std::sync::Arc::new(CaseQueue::new(concurrent_cases));
         let (tx, rx) = std::sync::mpsc::channel::<notify::Event>();
-        let mut remaining = std::collections::VecDeque::from(concurrent_cases);
-        while pending > 0 || !remaining.is_empty() {
-            while pending < threads && !remaining.is_empty() {
-                let case = remaining.pop_front().unwrap();
-                let name = case.name().to_owned();
-
-                let cfg = std::thread::Builder::new().name(name.clone());
-                let start = *start;
-                let tx = tx.clone();
-                let case = std::sync::Arc::new(case);
-                let case_fallback = case.clone();
-                let context = context.clone();
-                let context_fallback = context.clone();
-                let sync_success = sync_success.clone();
-                let sync_success_fallback = sync_success.clone();
-                let join_handle = cfg.spawn(move || {
-                    let mut notifier = SenderNotifier { tx: tx.clone() };
+        let mut workers = 0;
+        for i in 0..threads.min(queue.len()) {
+            let cfg = std::thread::Builder::new().name(format!("libtest2-worker-{i}"));
+            let start = *start;
+            let tx = tx.clone();
+            let queue = queue.clone();
+            let context = context.clone();
+            let hooks = hooks.clone();
+            let sync_success = sync_success.clone();
+            let worker = cfg.spawn(move || {
+                let mut notifier = SenderNotifier { tx };
+                while let Some(case) = queue.next() {
                     let case_success =
-                        run_case(&start, case.as_ref().as_ref(), &context, &mut notifier)
-                            .expect("`SenderNotifier` is infallible");
+                        run_queued_case(&start, case, &context, &hooks, &mut notifier);
                     if !case_success {
                         sync_success.store(case_success, std::sync::atomic::Ordering::Relaxed);
                     }
-                });
-                match join_handle {
-                    Ok(join_handle) => {
-                        running_tests.insert(name.clone(), RunningTest { join_handle });
-                        pending += 1;
-                    }
-                    Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
-                        // `ErrorKind::WouldBlock` means hitting the thread limit on some
-                        // platforms, so run the test synchronously here instead.
-                        let case_success = run_case(
-                            &start,
-                            case_fallback.as_ref().as_ref(),
-                            &context_fallback,
-                            notifier,
-                        )
-                        .expect("`SenderNotifier` is infallible");
-                        if !case_success {
-                            sync_success_fallback
-                                .store(case_success, std::sync::atomic::Ordering::Relaxed);
-                        }
-                    }
-                    Err(e) => {
-                        return Err(e);
-                    }
+                }
+            });
+            match worker {
+                Ok(_) => {
+                    workers += 1;
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                    // `ErrorKind::WouldBlock` means hitting the thread limit on some
+                    // platforms, so make do with the workers we have.
+                    break;
+                }
+                Err(e) => {
+                    return Err(e);
                 }
             }
+        }
+        // Only workers may hold a sender so `rx` closes once they have all exited
+        drop(tx);
+        // The main thread runs its own cases, or all of them without any workers
+        let used_threads = workers + usize::from(workers == 0 || !main_thread_cases.is_empty());
+        notifier.notify(run_start(used_threads))?;
 
-            let event = rx.recv().unwrap();
-            if let notify::Event::CaseComplete(event) = &event {
-                let running_test = running_tests.remove(&event.name).unwrap();
-                running_test.join(start, event, notifier)?;
-                pending -= 1;
+        // Run these while the workers are busy, reporting their progress between cases
+        let mut main_thread_cases = main_thread_cases.into_iter();
+        loop {
+            for event in rx.try_iter() {
+                track_running(&mut running, &event);
+                notifier.notify(event)?;
             }
-            notifier.notify(event)?;
             success &= sync_success.load(std::sync::atomic::Ordering::SeqCst);
-            if !success && opts.fail_fast {
+            if should_stop(success) {
+                break;
+            }
+            let Some(case) = main_thread_cases.next() else {
                 break;
+            };
+            success &= run_case(start, case.as_ref(), &context, &hooks, notifier)?;
+        }
+        not_run.extend(main_thread_cases.map(|case| case.name().to_owned()));
+        if !success && opts.fail_fast {
+            queue.close();
+            context.cancel();
+        }
+
+        if workers == 0 {
+            // Not even one worker could be spawned, so run the tests synchronously here instead.
+            while !should_stop(success) {
+                let Some(case) = queue.next() else {
+                    break;
+                };
+                success &= run_case(start, case, &context, &hooks, notifier)?;
+            }
+            queue.close();
+        } else {
+            let mut grace_deadline = None;
+            loop {
+                // Checked on every poll as a worker records its failure after the case's events
+                success &= sync_success.load(std::sync::atomic::Ordering::SeqCst);
+                if !success && opts.fail_fast {
+                    // Don't start any more cases but let running ones report their results
+                    queue.close();
+                    context.cancel();
+                }
+                if context.is_interrupted() {
+                    // Don't start any more cases but give running ones a chance to finish
+                    queue.close();
+                    if grace_expired(&context, &mut grace_deadline) {
+                        break;
+                    }
+                }
+
+                let event = match rx.recv_timeout(INTERRUPT_POLL) {
+                    Ok(event) => event,
+                    Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                        notifier.tick()?;
+                        continue;
+                    }
+                    Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+                };
+                track_running(&mut running, &event);
+                notifier.notify(event)?;
             }
         }
+
+        not_run.extend(queue.not_started().map(|case| case.name().to_owned()));
+    } else {
+        notifier.notify(run_start(1))?;
     }
 
     if !exclusive_cases.is_empty() {
         notifier.threaded(false);
-        for case in exclusive_cases {
-            success &= run_case(start, case.as_ref(), &context, notifier)?;
-            if !success && opts.fail_fast {
+        // Only an interrupt needs the main thread free to enforce the grace period
+        let off_main_thread = cfg!(feature = "signals") && opts.test_threads.is_some();
+        let mut exclusive_cases = exclusive_cases.into_iter();
+        while !should_stop(success) {
+            let Some(case) = exclusive_cases.next() else {
                 break;
-            }
+            };
+            let case_success = match case {
+                RegisteredCase::Shared(case) if off_main_thread => {
+                    run_exclusive_case(start, case, &context, &hooks, &mut running, notifier)?
+                }
+                // Main thread cases can't be moved, so their grace period can't be enforced
+                case => run_case(start, case.as_case(), &context, &hooks, notifier)?,
+            };
+            success &= case_success;
         }
+        not_run.extend(exclusive_cases.map(|case| case.as_case().name().to_owned()));
+    }
+
+    // Only non-empty if interrupted cases outlived their grace period
+    let mut running = running.into_iter().collect::<Vec<_>>();
+    running.sort();
+    for name in running {
+        notifier.notify(
+            notify::event::CaseMessage {
+                name: name.clone(),
+                kind: notify::MessageKind::Cancelled,
+                message: Some("interrupted".to_owned()),
+                location: None,
+                causes: Vec::new(),
+                expected: None,
+                actual: None,
+                left: None,
+                right: None,
+                backtrace: None,
+                elapsed_s: Some(notify::Elapsed(start.elapsed())),
+            }
+            .into(),
+        )?;
+        notifier.notify(
+            notify::event::CaseComplete {
+                name,
+                scratch_dir: None,
+                elapsed_s: Some(notify::Elapsed(start.elapsed())),
+            }
+            .into(),
+        )?;
     }
 
+    not_run.sort();
+    for name in not_run {
+        notifier.notify(
+            notify::event::CaseMessage {
+                name: name.clone(),
+                kind: notify::MessageKind::NotRun,
+                message: None,
+                location: None,
+                causes: Vec::new(),
+                expected: None,
+                actual: None,
+                left: None,
+                right: None,
+                backtrace: None,
+                elapsed_s: Some(notify::Elapsed(start.elapsed())),
+            }
+            .into(),
+        )?;
+        notifier.notify(
+            notify::event::CaseComplete {
+                name,
+                scratch_dir: None,
+                elapsed_s: Some(notify::Elapsed(start.elapsed())),
+            }
+            .into(),
+        )?;
+    }
+
+    let interrupted = context.is_interrupted();
     notifier.notify(
         notify::event::RunComplete {
+            interrupted,
             elapsed_s: Some(notify::Elapsed(start.elapsed())),
         }
         .into(),
     )?;
 
-    Ok(success)
+    Ok(success && !interrupted)
+}
+
+/// Pick a different seed for each run, without pulling in a random number generator
+fn random_seed() -> u64 {
+    use std::hash::BuildHasher as _;
+    use std::hash::Hasher as _;
+
+    let mut hasher = std::collections::hash_map::RandomState::new().build_hasher();
+    if let Ok(now) = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+        hasher.write_u128(now.as_nanos());
+    }
+    hasher.finish()
+}
+
+/// How often to check for an interrupt while waiting on workers
+const INTERRUPT_POLL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Track which top-level cases have started but not completed
+fn track_running(running: &mut std::collections::HashSet<String>, event: &notify::Event) {
+    match event {
+        notify::Event::CaseStart(event) if event.parent.is_none() => {
+            running.insert(event.name.clone());
+        }
+        notify::Event::CaseComplete(event) => {
+            running.remove(&event.name);
+        }
+        _ => {}
+    }
 }
 
 fn run_case(
     start: &std::time::Instant,
     case: &dyn Case,
     context: &TestContext,
+    hooks: &Hooks,
     notifier: &mut dyn notify::Notifier,
 ) -> std::io::Result<bool> {
     notifier.notify(
         notify::event::CaseStart {
             name: case.name().to_owned(),
+            parent: None,
             elapsed_s: Some(notify::Elapsed(start.elapsed())),
         }
         .into(),
     )?;
 
+    let context = &context.for_case(case.name());
+
+    let mut errors = Vec::new();
+    let ignored = if case.ignored() {
+        context.ignore().err()
+    } else {
+        None
+    };
+    if let Some(ignored) = ignored {
+        // Skipped before any hooks run, so there is nothing to clean up after
+        errors.push(ignored);
+    } else {
+        let mut outcome = Ok(());
+        for hook in &hooks.before {
+            if outcome.is_err() {
+                break;
+            }
+            outcome = catch_panic(|| hook(case.name(), context));
+        }
+        if outcome.is_ok() {
+            outcome = catch_panic(|| case.run(context));
+        }
+        errors.extend(context.take_errors());
+        errors.extend(outcome.err());
+        for hook in &hooks.after {
+            errors.extend(catch_panic(|| hook(case.name(), context)).err());
+            errors.extend(context.take_errors());
+        }
+    }
+
+    for event in context.take_events() {
+        notifier.notify(event)?;
+    }
+    let (success, events) = context.complete(errors);
+    for event in events {
+        notifier.notify(event)?;
+    }
+
+    Ok(success)
+}
+
+pub(crate) fn catch_panic(f: impl FnOnce() -> RunResult) -> RunResult {
+    static INSTALL_HOOK: std::sync::Once = std::sync::Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let report = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(|c| c.get()) == 0 {
+                // Not from a case, leave it to the default reporting
+                report(info);
+                return;
+            }
+            // Attribute the panic to the case rather than reporting it to stderr, interleaved
+            // with other threads
+            let location = info.location().map(|l| l.to_string());
+            let backtrace = std::backtrace::Backtrace::capture();
+            let backtrace = (backtrace.status() == std::backtrace::BacktraceStatus::Captured)
+                .then(|| short_backtrace(&backtrace.to_string()));
+            CAUGHT_PANIC.with(|p| *p.borrow_mut() = Some((location, backtrace)));
+        }));
+    });
+
+    CATCHING.with(|c| c.set(c.get() + 1));
     let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-        __rust_begin_short_backtrace(|| case.run(context))
-    }))
-    .unwrap_or_else(|e| {
+        __rust_begin_short_backtrace(f)
+    }));
+    CATCHING.with(|c| c.set(c.get() - 1));
+    outcome.unwrap_or_else(|e| {
         // The `panic` information is just an `Any` object representing the
         // value the panic was invoked with. For most panics (which use
         // `panic!` like `println!`), this is either `&str` or `String`.
@@ -452,38 +929,75 @@ fn run_case(
             .map(|s| s.as_str())
             .or_else(|| e.downcast_ref::<&str>().copied());
 
-        let msg = match payload {
-            Some(payload) => format!("test panicked: {payload}"),
-            None => "test panicked".to_owned(),
+        let mut err = match payload {
+            Some(payload) => match parse_assert_eq(payload) {
+                Some((assertion, left, right)) => {
+                    RunError::fail(format!("test panicked: {assertion}"))
+                        .with_left_right(left, right)
+                }
+                None => RunError::fail(format!("test panicked: {payload}")),
+            },
+            None => RunError::fail("test panicked"),
         };
-        Err(RunError::fail(msg))
-    });
+        if let Some((location, backtrace)) = CAUGHT_PANIC.with(|p| p.borrow_mut().take()) {
+            err = err.with_panic_details(location, backtrace);
+        }
+        Err(err)
+    })
+}
 
-    let mut case_status = None;
-    if let Some(err) = outcome.as_ref().err() {
-        let kind = err.status();
-        case_status = Some(kind);
-        let message = err.cause().map(|c| c.to_string());
-        notifier.notify(
-            notify::event::CaseMessage {
-                name: case.name().to_owned(),
-                kind,
-                message,
-                elapsed_s: Some(notify::Elapsed(start.elapsed())),
-            }
-            .into(),
-        )?;
+std::thread_local! {
+    /// How many [`catch_panic`]s this thread is within
+    static CATCHING: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    /// Location and backtrace of the last panic on this thread within [`catch_panic`]
+    #[allow(clippy::type_complexity)]
+    static CAUGHT_PANIC: std::cell::RefCell<Option<(Option<String>, Option<String>)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Trim a backtrace to the frames between the panic machinery and the harness, like the default
+/// panic hook does unless `RUST_BACKTRACE=full`
+fn short_backtrace(backtrace: &str) -> String {
+    if std::env::var_os("RUST_BACKTRACE").is_some_and(|v| v == "full") {
+        return backtrace.trim_end().to_owned();
     }
 
-    notifier.notify(
-        notify::event::CaseComplete {
-            name: case.name().to_owned(),
-            elapsed_s: Some(notify::Elapsed(start.elapsed())),
+    // Each frame starts with its index, e.g. `  12: path::to::function`, followed by its location
+    let mut frames = Vec::<Vec<&str>>::new();
+    for line in backtrace.lines() {
+        let is_frame = line
+            .trim_start()
+            .split_once(": ")
+            .is_some_and(|(index, _)| index.chars().all(|c| c.is_ascii_digit()));
+        match frames.last_mut() {
+            Some(frame) if !is_frame => frame.push(line),
+            _ => frames.push(vec![line]),
         }
-        .into(),
-    )?;
+    }
+    let contains = |frame: &Vec<&str>, marker: &str| frame.iter().any(|l| l.contains(marker));
+    if let Some(end) = frames
+        .iter()
+        .position(|f| contains(f, "__rust_begin_short_backtrace"))
+    {
+        frames.truncate(end);
+    }
+    if let Some(start) = frames
+        .iter()
+        .rposition(|f| contains(f, "__rust_end_short_backtrace"))
+    {
+        frames.drain(..=start);
+    }
+    frames.concat().join("\n")
+}
 
-    Ok(case_status != Some(notify::MessageKind::Error))
+/// Split an `assert_eq!` message into the assertion and its left and right values
+fn parse_assert_eq(payload: &str) -> Option<(&str, &str, &str)> {
+    if !payload.starts_with("assertion `left == right` failed") {
+        return None;
+    }
+    let (assertion, rest) = payload.split_once("\n  left: ")?;
+    let (left, right) = rest.split_once("\n right: ")?;
+    Some((assertion, left, right))
 }
 
 /// Fixed frame used to clean the backtrace with `RUST_BACKTRACE=1`.
@@ -495,6 +1009,147 @@ fn __rust_begin_short_backtrace<T, F: FnOnce() -> T>(f: F) -> T {
     std::hint::black_box(result)
 }
 
+/// Whether an interrupted run has waited out its grace period
+///
+/// The grace period starts on the first check after the interrupt.
+fn grace_expired(context: &TestContext, grace_deadline: &mut Option<std::time::Instant>) -> bool {
+    if !context.is_interrupted() {
+        return false;
+    }
+    let deadline = *grace_deadline
+        .get_or_insert_with(|| std::time::Instant::now() + context.interrupt_grace());
+    deadline <= std::time::Instant::now()
+}
+
+/// Run an exclusive `case` on a thread named after it while the main thread waits on it
+///
+/// If the run is interrupted and the case outlives the grace period, it is left running and
+/// stays in `running`.
+fn run_exclusive_case(
+    start: &std::time::Instant,
+    case: Box<dyn Case + Send + Sync>,
+    context: &std::sync::Arc<TestContext>,
+    hooks: &std::sync::Arc<Hooks>,
+    running: &mut std::collections::HashSet<String>,
+    notifier: &mut dyn notify::Notifier,
+) -> std::io::Result<bool> {
+    let case: std::sync::Arc<dyn Case + Send + Sync> = case.into();
+    let (tx, rx) = std::sync::mpsc::channel::<notify::Event>();
+    let spawned = {
+        let start = *start;
+        let case = case.clone();
+        let context = context.clone();
+        let hooks = hooks.clone();
+        std::thread::Builder::new()
+            .name(case.name().to_owned())
+            .spawn(move || {
+                let mut notifier = SenderNotifier { tx };
+                run_queued_case(&start, case.as_ref(), &context, &hooks, &mut notifier)
+            })
+    };
+    let Ok(handle) = spawned else {
+        // e.g. hitting the thread limit, so run it here instead
+        return run_case(start, case.as_ref(), context, hooks, notifier);
+    };
+
+    let mut grace_deadline = None;
+    loop {
+        if grace_expired(context, &mut grace_deadline) {
+            return Ok(false);
+        }
+        let event = match rx.recv_timeout(INTERRUPT_POLL) {
+            Ok(event) => event,
+            Err(std::sync::mpsc::RecvTimeoutError::Timeout) => {
+                notifier.tick()?;
+                continue;
+            }
+            Err(std::sync::mpsc::RecvTimeoutError::Disconnected) => break,
+        };
+        track_running(running, &event);
+        notifier.notify(event)?;
+    }
+    Ok(handle.join().unwrap_or(false))
+}
+
+/// Run a case on a worker, reporting it as failed if the harness itself panics
+fn run_queued_case(
+    start: &std::time::Instant,
+    case: &dyn Case,
+    context: &TestContext,
+    hooks: &Hooks,
+    notifier: &mut SenderNotifier,
+) -> bool {
+    let mut case_success = false;
+    let outcome = catch_panic(|| {
+        case_success = run_case(start, case, context, hooks, notifier)
+            .expect("`SenderNotifier` is infallible");
+        Ok(())
+    });
+    if let Err(err) = outcome {
+        // The case never completed; report it so the run can still finish
+        let name = case.name().to_owned();
+        let elapsed_s = Some(notify::Elapsed(start.elapsed()));
+        let _ = notifier
+            .tx
+            .send(err.to_event(name.clone(), elapsed_s).into());
+        let _ = notifier.tx.send(
+            notify::event::CaseComplete {
+                name,
+                scratch_dir: None,
+                elapsed_s,
+            }
+            .into(),
+        );
+    }
+    case_success
+}
+
+/// Cases shared between workers, handed out in order
+struct CaseQueue {
+    cases: Vec<Box<dyn Case + Send + Sync>>,
+    next: std::sync::atomic::AtomicUsize,
+    closed_at: std::sync::atomic::AtomicUsize,
+}
+
+impl CaseQueue {
+    fn new(cases: Vec<Box<dyn Case + Send + Sync>>) -> Self {
+        Self {
+            cases,
+            next: Default::default(),
+            closed_at: std::sync::atomic::AtomicUsize::new(usize::MAX),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.cases.len()
+    }
+
+    fn next(&self) -> Option<&dyn Case> {
+        let index = self.next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        Some(self.cases.get(index)?.as_ref())
+    }
+
+    /// Stop handing out cases
+    fn close(&self) {
+        let next = self
+            .next
+            .fetch_max(self.cases.len(), std::sync::atomic::Ordering::Relaxed);
+        self.closed_at
+            .fetch_min(next, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Cases that were never handed out before the queue was closed
+    fn not_started(&self) -> impl Iterator<Item = &dyn Case> {
+        let closed_at = self
+            .closed_at
+            .load(std::sync::atomic::Ordering::Relaxed)
+            .min(self.cases.len());
+        self.cases[closed_at..]
+            .iter()
+            .map(|case| case.as_ref() as &dyn Case)
+    }
+}
+
 #[derive(Clone, Debug)]
 struct SenderNotifier {
     tx: std::sync::mpsc::Sender<notify::Event>,
@@ -27,6 +27,8 @@
 
 mod case;
 mod context;
+mod executor;
+mod filter;
 mod harness;
 mod notify;
 
@@ -34,6 +36,7 @@ pub mod cli;
 
 pub use case::*;
 pub use context::*;
+pub use executor::LocalBoxFuture;
 pub use harness::*;
 pub use notify::RunMode;
 
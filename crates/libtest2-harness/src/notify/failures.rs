@@ -0,0 +1,103 @@
+use super::Event;
+use super::MessageKind;
+
+/// Names of the cases that failed in the previous run, for `--rerun-failed`
+///
+/// Stored as one name per line, in the order the cases failed.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct Failures {
+    names: Vec<String>,
+}
+
+impl Failures {
+    pub(crate) fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                std::io::Error::new(
+                    err.kind(),
+                    format!("no failures recorded at {}", path.display()),
+                )
+            } else {
+                err
+            }
+        })?;
+        let names = content.lines().map(|name| name.to_owned()).collect();
+        Ok(Self { names })
+    }
+
+    /// Record the failures, clearing any left from an earlier run if there are none
+    pub(crate) fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        if self.names.is_empty() {
+            return match std::fs::remove_file(path) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut content = String::new();
+        for name in &self.names {
+            content.push_str(name);
+            content.push('\n');
+        }
+        std::fs::write(path, content)
+    }
+
+    pub(crate) fn position(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+}
+
+/// Record which cases fail, saving their names when the run completes
+pub(crate) struct FailuresNotifier {
+    inner: Box<dyn super::Notifier>,
+    path: std::path::PathBuf,
+    failures: Failures,
+    status: std::collections::HashMap<String, Option<MessageKind>>,
+}
+
+impl FailuresNotifier {
+    pub(crate) fn new(inner: Box<dyn super::Notifier>, path: std::path::PathBuf) -> Self {
+        Self {
+            inner,
+            path,
+            failures: Default::default(),
+            status: Default::default(),
+        }
+    }
+}
+
+impl super::Notifier for FailuresNotifier {
+    fn threaded(&mut self, yes: bool) {
+        self.inner.threaded(yes);
+    }
+
+    fn tick(&mut self) -> std::io::Result<()> {
+        self.inner.tick()
+    }
+
+    fn notify(&mut self, event: Event) -> std::io::Result<()> {
+        match &event {
+            Event::CaseStart(inner) if inner.parent.is_none() => {
+                self.status.insert(inner.name.clone(), None);
+            }
+            Event::CaseMessage(inner) => {
+                if let Some(status) = self.status.get_mut(&inner.name) {
+                    *status = (*status).max(Some(inner.kind));
+                }
+            }
+            Event::CaseComplete(inner) => {
+                if let Some(Some(MessageKind::Error)) = self.status.remove(&inner.name) {
+                    self.failures.names.push(inner.name.clone());
+                }
+            }
+            Event::RunComplete(_) => {
+                // Not worth failing the run over, there just won't be anything to rerun
+                let _ = self.failures.save(&self.path);
+            }
+            _ => {}
+        }
+        self.inner.notify(event)
+    }
+}
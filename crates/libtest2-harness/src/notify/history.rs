@@ -0,0 +1,240 @@
+use super::Elapsed;
+use super::Event;
+use super::MessageKind;
+use super::FAILED;
+use super::IGNORED;
+use super::OK;
+
+/// How many of the latest runs count as recent
+const RECENT: usize = 10;
+
+/// Outcomes and durations of cases across runs
+///
+/// Stored as the json events of each run, appended one run after another.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct History {
+    cases: std::collections::BTreeMap<String, Vec<Outcome>>,
+    running: std::collections::HashMap<String, (Elapsed, Option<MessageKind>)>,
+}
+
+impl History {
+    /// Replay the events at `path`, treating a missing file as empty
+    pub(crate) fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        let mut history = Self::default();
+        for (i, line) in content.lines().enumerate() {
+            let event = Event::from_jsonline(line).map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("{}:{}: {err}", path.display(), i + 1),
+                )
+            })?;
+            history.record(&event);
+        }
+        Ok(history)
+    }
+
+    /// Record the outcome of each top-level case as its events arrive
+    fn record(&mut self, event: &Event) {
+        match event {
+            Event::RunStart(_) => {
+                // Cases left running by an earlier run never completed
+                self.running.clear();
+            }
+            Event::CaseStart(inner) if inner.parent.is_none() => {
+                if let Some(elapsed_s) = inner.elapsed_s {
+                    self.running.insert(inner.name.clone(), (elapsed_s, None));
+                }
+            }
+            Event::CaseMessage(inner) => {
+                if let Some((_, status)) = self.running.get_mut(&inner.name) {
+                    *status = (*status).max(Some(inner.kind));
+                }
+            }
+            Event::CaseComplete(inner) => {
+                if let (Some((started, status)), Some(completed)) =
+                    (self.running.remove(&inner.name), inner.elapsed_s)
+                {
+                    let duration = completed.0.saturating_sub(started.0);
+                    self.cases
+                        .entry(inner.name.clone())
+                        .or_default()
+                        .push(Outcome { status, duration });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Summarize the runs of `name`, oldest first
+    pub(crate) fn write_report(
+        &self,
+        writer: &mut dyn std::io::Write,
+        name: &str,
+    ) -> std::io::Result<()> {
+        let Some(outcomes) = self.cases.get(name) else {
+            return write!(writer, "no history");
+        };
+
+        // Only passes and failures say anything about flakiness
+        let decided = outcomes
+            .iter()
+            .filter(|o| matches!(o.status, None | Some(MessageKind::Error)))
+            .collect::<Vec<_>>();
+        let passed = decided.iter().filter(|o| o.status.is_none()).count();
+        write!(writer, "{passed}/{} passed", decided.len())?;
+
+        let recent = &decided[decided.len().saturating_sub(RECENT)..];
+        let flips = recent
+            .windows(2)
+            .filter(|pair| pair[0].status != pair[1].status)
+            .count();
+        let s = if flips == 1 { "" } else { "s" };
+        write!(writer, "; {flips} recent flip{s} ")?;
+        for outcome in &outcomes[outcomes.len().saturating_sub(RECENT)..] {
+            let (c, style) = match outcome.status {
+                Some(MessageKind::Ignored) => ('i', IGNORED),
+                Some(MessageKind::Cancelled) => ('c', IGNORED),
+                Some(MessageKind::NotRun) => ('n', IGNORED),
+                Some(MessageKind::Error) => ('F', FAILED),
+                None => ('.', OK),
+            };
+            write!(writer, "{style}{c}{style:#}")?;
+        }
+
+        if !recent.is_empty() {
+            let durations = recent
+                .iter()
+                .map(|o| o.duration.as_secs_f64())
+                .collect::<Vec<_>>();
+            let mean = durations.iter().sum::<f64>() / durations.len() as f64;
+            write!(
+                writer,
+                "; {} mean",
+                Elapsed(std::time::Duration::from_secs_f64(mean))
+            )?;
+            if let Some(slope) = trend(&durations) {
+                let sign = if slope < 0.0 { '-' } else { '+' };
+                let slope = Elapsed(std::time::Duration::from_secs_f64(slope.abs()));
+                write!(writer, ", {sign}{slope} per run")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The least-squares change in seconds from one run to the next
+fn trend(durations: &[f64]) -> Option<f64> {
+    if durations.len() < 2 {
+        return None;
+    }
+    let n = durations.len() as f64;
+    let mean_x = (n - 1.0) / 2.0;
+    let mean_y = durations.iter().sum::<f64>() / n;
+    let mut covariance = 0.0;
+    let mut variance = 0.0;
+    for (x, y) in durations.iter().enumerate() {
+        let dx = x as f64 - mean_x;
+        covariance += dx * (y - mean_y);
+        variance += dx * dx;
+    }
+    Some(covariance / variance)
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Outcome {
+    status: Option<MessageKind>,
+    duration: std::time::Duration,
+}
+
+/// Append the events of the run to the history once it completes
+pub(crate) struct HistoryNotifier {
+    inner: Box<dyn super::Notifier>,
+    path: std::path::PathBuf,
+    run: String,
+}
+
+impl HistoryNotifier {
+    pub(crate) fn new(inner: Box<dyn super::Notifier>, path: std::path::PathBuf) -> Self {
+        Self {
+            inner,
+            path,
+            run: Default::default(),
+        }
+    }
+}
+
+impl super::Notifier for HistoryNotifier {
+    fn threaded(&mut self, yes: bool) {
+        self.inner.threaded(yes);
+    }
+
+    fn tick(&mut self) -> std::io::Result<()> {
+        self.inner.tick()
+    }
+
+    fn notify(&mut self, event: Event) -> std::io::Result<()> {
+        self.run.push_str(&event.to_jsonline());
+        self.run.push('\n');
+        if let Event::RunComplete(_) = &event {
+            use std::io::Write as _;
+
+            // Written all at once so an interrupted process doesn't leave half a run
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&self.path)?;
+            file.write_all(self.run.as_bytes())?;
+        }
+        self.inner.notify(event)
+    }
+}
+
+/// List cases along with a summary of their history
+#[derive(Debug)]
+pub(crate) struct HistoryListNotifier<W> {
+    writer: W,
+    history: History,
+    tests: usize,
+}
+
+impl<W: std::io::Write> HistoryListNotifier<W> {
+    pub(crate) fn new(writer: W, history: History) -> Self {
+        Self {
+            writer,
+            history,
+            tests: 0,
+        }
+    }
+}
+
+impl<W: std::io::Write> super::Notifier for HistoryListNotifier<W> {
+    fn notify(&mut self, event: Event) -> std::io::Result<()> {
+        match event {
+            Event::DiscoverStart(_) => {}
+            Event::DiscoverCase(inner) => {
+                if inner.selected {
+                    write!(self.writer, "{}: ", inner.name)?;
+                    self.history.write_report(&mut self.writer, &inner.name)?;
+                    writeln!(self.writer)?;
+                    self.tests += 1;
+                }
+            }
+            Event::DiscoverComplete(_) => {
+                writeln!(self.writer)?;
+                writeln!(self.writer, "{} tests", self.tests)?;
+                writeln!(self.writer)?;
+            }
+            Event::RunStart(_) => {}
+            Event::CaseStart(_) => {}
+            Event::CaseMessage(_) => {}
+            Event::CaseComplete(_) => {}
+            Event::RunComplete(_) => {}
+        }
+        Ok(())
+    }
+}
@@ -1,26 +1,50 @@
+mod failures;
+#[cfg(feature = "json")]
+mod history;
 #[cfg(feature = "json")]
 mod json;
 #[cfg(not(feature = "color"))]
 mod no_style;
 mod pretty;
+mod progress;
 #[cfg(feature = "color")]
 mod style;
 mod summary;
+#[cfg(feature = "json")]
+mod tee;
 mod terse;
+mod timings;
+mod tree;
 
+pub(crate) use failures::*;
+#[cfg(feature = "json")]
+pub(crate) use history::*;
 #[cfg(feature = "json")]
 pub(crate) use json::*;
 #[cfg(not(feature = "color"))]
 pub(crate) use no_style::*;
 pub(crate) use pretty::*;
+pub(crate) use progress::*;
 #[cfg(feature = "color")]
 pub(crate) use style::*;
 pub(crate) use summary::*;
+#[cfg(feature = "json")]
+pub(crate) use tee::*;
 pub(crate) use terse::*;
+pub(crate) use timings::*;
+pub(crate) use tree::*;
 
 pub(crate) trait Notifier {
     fn threaded(&mut self, _yes: bool) {}
 
+    /// Called periodically while waiting on cases running on other threads
+    ///
+    /// This includes cases run one at a time, e.g. with `--test-threads 1`, only when the `signals`
+    /// feature moves them off the main thread.  Otherwise nothing else runs meanwhile.
+    fn tick(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+
     fn notify(&mut self, event: Event) -> std::io::Result<()>;
 }
 
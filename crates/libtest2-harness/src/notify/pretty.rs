@@ -10,6 +10,14 @@ pub(crate) struct PrettyRunNotifier<W> {
     is_multithreaded: bool,
     summary: super::Summary,
     name_width: usize,
+    /// Case whose status is pending at the end of the current line
+    open_line: Option<String>,
+    /// How deeply each running sub-case is nested
+    ///
+    /// Sub-cases aren't known at discovery, so they are indented under their parent rather than
+    /// aligned with the cases.
+    subcase_depth: std::collections::HashMap<String, usize>,
+    report_time: bool,
 }
 
 impl<W: std::io::Write> PrettyRunNotifier<W> {
@@ -19,6 +27,27 @@ impl<W: std::io::Write> PrettyRunNotifier<W> {
             is_multithreaded: false,
             summary: Default::default(),
             name_width: 0,
+            open_line: None,
+            subcase_depth: Default::default(),
+            report_time: false,
+        }
+    }
+
+    /// Report how long each case took, and the slowest cases once the run completes
+    pub(crate) fn report_time(mut self, yes: bool) -> Self {
+        self.report_time = yes;
+        self.summary.report_time(yes);
+        self
+    }
+
+    fn write_name(&mut self, name: &str) -> std::io::Result<()> {
+        match self.subcase_depth.get(name) {
+            Some(depth) => {
+                let indent = "    ".repeat(*depth);
+                let short_name = name.rsplit("::").next().unwrap_or(name);
+                write!(self.writer, "{indent}{short_name} ... ")
+            }
+            None => write!(self.writer, "test {: <1$} ... ", name, self.name_width),
         }
     }
 }
@@ -42,13 +71,18 @@ impl<W: std::io::Write> super::Notifier for PrettyRunNotifier<W> {
                 self.summary.write_start(&mut self.writer)?;
             }
             Event::CaseStart(inner) => {
+                if let Some(parent) = &inner.parent {
+                    let depth = self.subcase_depth.get(parent).copied().unwrap_or(0) + 1;
+                    self.subcase_depth.insert(inner.name.clone(), depth);
+                }
                 if !self.is_multithreaded {
-                    write!(
-                        self.writer,
-                        "test {: <1$} ... ",
-                        inner.name, self.name_width
-                    )?;
+                    if self.open_line.is_some() {
+                        // A sub-case started, the parent's status will be reported on its own line
+                        writeln!(self.writer)?;
+                    }
+                    self.write_name(&inner.name)?;
                     self.writer.flush()?;
+                    self.open_line = Some(inner.name);
                 }
             }
             Event::CaseMessage(_) => {}
@@ -56,18 +90,23 @@ impl<W: std::io::Write> super::Notifier for PrettyRunNotifier<W> {
                 let status = self.summary.get_kind(&inner.name);
                 let (s, style) = match status {
                     Some(MessageKind::Ignored) => ("ignored", IGNORED),
+                    Some(MessageKind::Cancelled) => ("cancelled", IGNORED),
+                    Some(MessageKind::NotRun) => ("not run", IGNORED),
                     Some(MessageKind::Error) => ("FAILED", FAILED),
                     None => ("ok", OK),
                 };
 
-                if self.is_multithreaded {
-                    write!(
-                        self.writer,
-                        "test {: <1$} ... ",
-                        inner.name, self.name_width
-                    )?;
+                if self.open_line.take().as_ref() != Some(&inner.name) {
+                    self.write_name(&inner.name)?;
+                }
+                self.subcase_depth.remove(&inner.name);
+                write!(self.writer, "{style}{s}{style:#}")?;
+                if self.report_time {
+                    if let Some(duration) = self.summary.get_duration(&inner.name) {
+                        write!(self.writer, " <{}>", super::Elapsed(duration))?;
+                    }
                 }
-                writeln!(self.writer, "{style}{s}{style:#}")?;
+                writeln!(self.writer)?;
             }
             Event::RunComplete(_) => {
                 self.summary.write_complete(&mut self.writer)?;
@@ -0,0 +1,413 @@
+use super::Elapsed;
+use super::Event;
+use super::MessageKind;
+use super::FAILED;
+use super::IGNORED;
+use super::OK;
+
+/// How many running cases to list before summarizing the rest
+const MAX_RUNNING: usize = 8;
+
+/// Report each result like [`PrettyRunNotifier`][super::PrettyRunNotifier], followed by the
+/// progress of the run and the cases still running, redrawn in place
+///
+/// `writer` and `terminal` must write to the same terminal.  Cursor movement goes to `terminal`
+/// so it isn't stripped when `writer` drops styling.
+#[derive(Debug)]
+pub(crate) struct ProgressRunNotifier<W, T> {
+    writer: W,
+    terminal: T,
+    summary: super::Summary,
+    name_width: usize,
+    total: usize,
+    completed: usize,
+    failed: usize,
+    /// Top-level cases that started but have yet to complete, in the order they started
+    running: Vec<(String, std::time::Instant)>,
+    subcases: std::collections::HashSet<String>,
+    /// Number of lines of the progress display currently on screen
+    drawn: Option<usize>,
+    report_time: bool,
+}
+
+impl<W: std::io::Write, T: std::io::Write> ProgressRunNotifier<W, T> {
+    pub(crate) fn new(writer: W, terminal: T) -> Self {
+        Self {
+            writer,
+            terminal,
+            summary: Default::default(),
+            name_width: 0,
+            total: 0,
+            completed: 0,
+            failed: 0,
+            running: Vec::new(),
+            subcases: Default::default(),
+            drawn: None,
+            report_time: false,
+        }
+    }
+
+    /// Report how long each case took, and the slowest cases once the run completes
+    pub(crate) fn report_time(mut self, yes: bool) -> Self {
+        self.report_time = yes;
+        self.summary.report_time(yes);
+        self
+    }
+
+    fn erase(&mut self) -> std::io::Result<()> {
+        if let Some(drawn) = self.drawn.take() {
+            self.writer.flush()?;
+            if drawn != 0 {
+                // Move to the start of the first line drawn and clear through the end of screen
+                write!(self.terminal, "\x1b[{drawn}F\x1b[J")?;
+            }
+            self.terminal.flush()?;
+        }
+        Ok(())
+    }
+
+    fn draw(&mut self) -> std::io::Result<()> {
+        let mut drawn = 1;
+        write!(self.writer, "{}/{} completed", self.completed, self.total)?;
+        if self.failed != 0 {
+            write!(self.writer, "; {FAILED}{} failed{FAILED:#}", self.failed)?;
+        }
+        writeln!(self.writer)?;
+
+        let now = std::time::Instant::now();
+        for (name, started) in self.running.iter().take(MAX_RUNNING) {
+            let elapsed = Elapsed(now.saturating_duration_since(*started));
+            writeln!(
+                self.writer,
+                "    {name: <0$} ... {elapsed}",
+                self.name_width
+            )?;
+            drawn += 1;
+        }
+        if MAX_RUNNING < self.running.len() {
+            writeln!(
+                self.writer,
+                "    ... and {} more",
+                self.running.len() - MAX_RUNNING
+            )?;
+            drawn += 1;
+        }
+
+        self.writer.flush()?;
+        self.drawn = Some(drawn);
+        Ok(())
+    }
+}
+
+impl<W: std::io::Write, T: std::io::Write> super::Notifier for ProgressRunNotifier<W, T> {
+    fn tick(&mut self) -> std::io::Result<()> {
+        if self.drawn.is_some() {
+            self.erase()?;
+            self.draw()?;
+        }
+        Ok(())
+    }
+
+    fn notify(&mut self, event: Event) -> std::io::Result<()> {
+        self.summary.notify(event.clone())?;
+        match event {
+            Event::DiscoverStart(_) => {}
+            Event::DiscoverCase(inner) => {
+                if inner.selected {
+                    self.name_width = inner.name.len().max(self.name_width);
+                    self.total += 1;
+                }
+            }
+            Event::DiscoverComplete(_) => {}
+            Event::RunStart(_) => {
+                self.summary.write_start(&mut self.writer)?;
+                self.draw()?;
+            }
+            Event::CaseStart(inner) => {
+                self.erase()?;
+                if inner.parent.is_some() {
+                    self.subcases.insert(inner.name);
+                } else {
+                    self.running.push((inner.name, std::time::Instant::now()));
+                }
+                self.draw()?;
+            }
+            Event::CaseMessage(_) => {}
+            Event::CaseComplete(inner) => {
+                self.erase()?;
+
+                let status = self.summary.get_kind(&inner.name);
+                let (s, style) = match status {
+                    Some(MessageKind::Ignored) => ("ignored", IGNORED),
+                    Some(MessageKind::Cancelled) => ("cancelled", IGNORED),
+                    Some(MessageKind::NotRun) => ("not run", IGNORED),
+                    Some(MessageKind::Error) => ("FAILED", FAILED),
+                    None => ("ok", OK),
+                };
+                write!(
+                    self.writer,
+                    "test {: <1$} ... {style}{s}{style:#}",
+                    inner.name, self.name_width
+                )?;
+                if self.report_time {
+                    if let Some(duration) = self.summary.get_duration(&inner.name) {
+                        write!(self.writer, " <{}>", Elapsed(duration))?;
+                    }
+                }
+                writeln!(self.writer)?;
+
+                if !self.subcases.remove(&inner.name) {
+                    self.running.retain(|(name, _)| *name != inner.name);
+                    self.completed += 1;
+                    if status == Some(MessageKind::Error) {
+                        self.failed += 1;
+                    }
+                }
+                self.draw()?;
+            }
+            Event::RunComplete(_) => {
+                self.erase()?;
+                self.summary.write_complete(&mut self.writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::notify::event;
+    use crate::notify::Notifier as _;
+    use crate::notify::RunMode;
+
+    use snapbox::str;
+
+    fn notifier() -> ProgressRunNotifier<Vec<u8>, Vec<u8>> {
+        ProgressRunNotifier::new(Vec::new(), Vec::new())
+    }
+
+    /// Run `events` through a notifier whose `writer` and `terminal` share one buffer
+    fn render(events: impl IntoIterator<Item = Option<Event>>) -> String {
+        let output = std::rc::Rc::new(std::cell::RefCell::new(Vec::new()));
+        let mut notifier = ProgressRunNotifier::new(Shared(output.clone()), Shared(output.clone()));
+        for event in events {
+            match event {
+                Some(event) => notifier.notify(event).unwrap(),
+                None => notifier.tick().unwrap(),
+            }
+        }
+        drop(notifier);
+        let output = output.borrow();
+        escape(&String::from_utf8_lossy(&output))
+    }
+
+    /// Make cursor movement visible, dropping styling as that depends on the `color` feature
+    fn escape(output: &str) -> String {
+        let mut escaped = String::new();
+        let mut rest = output;
+        while let Some((before, after)) = rest.split_once('\x1b') {
+            escaped.push_str(before);
+            let end = after
+                .find(|c: char| c.is_ascii_alphabetic())
+                .map(|i| i + 1)
+                .unwrap_or(after.len());
+            let (sequence, after) = after.split_at(end);
+            if !sequence.ends_with('m') {
+                escaped.push('␛');
+                escaped.push_str(sequence);
+            }
+            rest = after;
+        }
+        escaped.push_str(rest);
+        escaped
+    }
+
+    struct Shared(std::rc::Rc<std::cell::RefCell<Vec<u8>>>);
+
+    impl std::io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn discover(name: &str) -> Option<Event> {
+        Some(
+            event::DiscoverCase {
+                name: name.to_owned(),
+                mode: RunMode::Test,
+                selected: true,
+                params: Default::default(),
+                tags: Vec::new(),
+                elapsed_s: None,
+            }
+            .into(),
+        )
+    }
+
+    fn run_start() -> Option<Event> {
+        Some(
+            event::RunStart {
+                seed: None,
+                threads: None,
+                elapsed_s: None,
+            }
+            .into(),
+        )
+    }
+
+    fn start(name: &str) -> Option<Event> {
+        Some(
+            event::CaseStart {
+                name: name.to_owned(),
+                parent: None,
+                elapsed_s: None,
+            }
+            .into(),
+        )
+    }
+
+    fn fail(name: &str) -> Option<Event> {
+        Some(
+            event::CaseMessage {
+                name: name.to_owned(),
+                kind: MessageKind::Error,
+                message: None,
+                location: None,
+                causes: Vec::new(),
+                expected: None,
+                actual: None,
+                left: None,
+                right: None,
+                backtrace: None,
+                elapsed_s: None,
+            }
+            .into(),
+        )
+    }
+
+    fn complete(name: &str) -> Option<Event> {
+        Some(
+            event::CaseComplete {
+                name: name.to_owned(),
+                scratch_dir: None,
+                elapsed_s: None,
+            }
+            .into(),
+        )
+    }
+
+    fn run_complete() -> Option<Event> {
+        Some(
+            event::RunComplete {
+                interrupted: false,
+                elapsed_s: None,
+            }
+            .into(),
+        )
+    }
+
+    const TICK: Option<Event> = None;
+
+    #[test]
+    fn redraws_in_place() {
+        let output = render([
+            discover("fast"),
+            discover("broken"),
+            discover("slow"),
+            run_start(),
+            start("slow"),
+            start("fast"),
+            TICK,
+            complete("fast"),
+            start("broken"),
+            fail("broken"),
+            complete("broken"),
+            TICK,
+            complete("slow"),
+            run_complete(),
+        ]);
+        snapbox::assert_data_eq!(
+            output,
+            str![[r#"
+
+running 3 tests
+0/3 completed
+␛[1F␛[J0/3 completed
+    slow   ... [..]s
+␛[2F␛[J0/3 completed
+    slow   ... [..]s
+    fast   ... [..]s
+␛[3F␛[J0/3 completed
+    slow   ... [..]s
+    fast   ... [..]s
+␛[3F␛[Jtest fast   ... ok
+1/3 completed
+    slow   ... [..]s
+␛[2F␛[J1/3 completed
+    slow   ... [..]s
+    broken ... [..]s
+␛[3F␛[Jtest broken ... FAILED
+2/3 completed; 1 failed
+    slow   ... [..]s
+␛[2F␛[J2/3 completed; 1 failed
+    slow   ... [..]s
+␛[2F␛[Jtest slow   ... ok
+3/3 completed; 1 failed
+␛[1F␛[J
+failures:
+
+
+failures:
+    broken
+
+to rerun the failures, pass `-Zunstable-options --rerun-failed`
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 filtered out
+
+
+"#]]
+        );
+    }
+
+    #[test]
+    fn many_running() {
+        let names = (0..10).map(|i| format!("case_{i}")).collect::<Vec<_>>();
+        let events = names
+            .iter()
+            .map(|name| discover(name))
+            .chain([run_start()])
+            .chain(names.iter().map(|name| start(name)))
+            .chain([TICK]);
+        let output = render(events);
+        let (_, last_frame) = output.rsplit_once("␛[J").unwrap();
+        snapbox::assert_data_eq!(
+            last_frame,
+            str![[r#"
+0/10 completed
+    case_0 ... [..]s
+    case_1 ... [..]s
+    case_2 ... [..]s
+    case_3 ... [..]s
+    case_4 ... [..]s
+    case_5 ... [..]s
+    case_6 ... [..]s
+    case_7 ... [..]s
+    ... and 2 more
+
+"#]]
+        );
+    }
+
+    #[test]
+    fn tick_before_run() {
+        let mut notifier = notifier();
+        notifier.tick().unwrap();
+        assert!(notifier.writer.is_empty());
+        assert!(notifier.terminal.is_empty());
+    }
+}
@@ -4,6 +4,12 @@ use super::MessageKind;
 use super::FAILED;
 use super::OK;
 
+/// How many of the slowest cases to report with `--report-time`
+const SLOWEST: usize = 5;
+
+/// Largest table [`line_diff`] builds, beyond which it would take too much memory
+const MAX_DIFF_CELLS: usize = 1_000_000;
+
 #[derive(Default, Clone, Debug)]
 pub(crate) struct Summary {
     num_run: usize,
@@ -12,15 +18,30 @@ pub(crate) struct Summary {
     num_filtered_out: usize,
 
     status: std::collections::HashMap<String, CaseStatus>,
+    params: std::collections::HashMap<String, std::collections::BTreeMap<String, String>>,
+    binary: Option<String>,
+    seed: Option<super::Seed>,
     elapsed_s: Option<super::Elapsed>,
+    interrupted: bool,
+    report_time: bool,
 }
 
 impl Summary {
+    /// Report the slowest cases once the run completes
+    pub(crate) fn report_time(&mut self, yes: bool) {
+        self.report_time = yes;
+    }
+
     pub(crate) fn get_kind(&self, name: &str) -> Option<MessageKind> {
         let status = self.status.get(name)?;
         find_run_status(status)
     }
 
+    /// How long the case took, from start to completion
+    pub(crate) fn get_duration(&self, name: &str) -> Option<std::time::Duration> {
+        self.status.get(name)?.duration
+    }
+
     pub(crate) fn write_start(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()> {
         let s = if self.num_run == 1 { "" } else { "s" };
 
@@ -30,39 +51,55 @@ impl Summary {
     }
 
     pub(crate) fn write_complete(&self, writer: &mut dyn ::std::io::Write) -> std::io::Result<()> {
-        let mut num_passed = 0;
-        let mut num_failed = 0;
-        let mut num_ignored = 0;
-        let mut failures = std::collections::BTreeMap::new();
+        // Sub-cases are counted separately as the running count is only known for cases
+        let mut cases = Counts::default();
+        let mut subcases = Counts::default();
+        let mut failures = std::collections::BTreeMap::<_, Vec<String>>::new();
         for (name, case_status) in &self.status {
             let mut status = find_run_status(case_status);
-            if !case_status.started {
+            if !case_status.started && status != Some(MessageKind::NotRun) {
                 // Even override `Ignored`
                 status = Some(MessageKind::Error);
-                failures.insert(name, Some("test found that never started"));
+                failures
+                    .entry(name)
+                    .or_default()
+                    .push("test found that never started".to_owned());
             }
             if !case_status.completed {
                 // Even override `Ignored`
                 status = Some(MessageKind::Error);
-                failures.insert(name, Some("test never completed"));
+                failures
+                    .entry(name)
+                    .or_default()
+                    .push("test never completed".to_owned());
             }
+            let counts = if case_status.subcase {
+                &mut subcases
+            } else {
+                &mut cases
+            };
             match status {
-                Some(MessageKind::Ignored) => num_ignored += 1,
+                Some(MessageKind::Ignored) => counts.ignored += 1,
+                Some(MessageKind::Cancelled) => counts.cancelled += 1,
+                Some(MessageKind::NotRun) => counts.not_run += 1,
                 Some(MessageKind::Error) => {
-                    num_failed += 1;
+                    counts.failed += 1;
+                    let messages = failures.entry(name).or_default();
                     for event in &case_status.messages {
                         if Some(event.kind) == status {
-                            failures.insert(name, event.message.as_deref());
+                            messages.extend(render_message(event));
                         }
                     }
                 }
-                None => num_passed += 1,
+                None => counts.passed += 1,
             }
         }
 
-        let has_failed = 0 < num_failed;
+        let has_failed = 0 < cases.failed + subcases.failed;
 
-        let (summary, summary_style) = if has_failed {
+        let (summary, summary_style) = if self.interrupted {
+            ("INTERRUPTED", FAILED)
+        } else if has_failed {
             ("FAILED", FAILED)
         } else {
             ("ok", OK)
@@ -76,10 +113,30 @@ impl Summary {
             writeln!(writer)?;
 
             // Print messages of all tests
-            for (name, msg) in &failures {
-                if let Some(msg) = msg {
+            for (name, messages) in &failures {
+                if !messages.is_empty() {
                     writeln!(writer, "---- {name} ----")?;
-                    writeln!(writer, "{msg}")?;
+                    if let Some(params) = self.params.get(*name) {
+                        let params = params
+                            .iter()
+                            .map(|(key, value)| format!("{key}={value}"))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        writeln!(writer, "parameters: {params}")?;
+                    }
+                    for (i, msg) in messages.iter().enumerate() {
+                        if i != 0 {
+                            writeln!(writer)?;
+                        }
+                        writeln!(writer, "{msg}")?;
+                    }
+                    if let Some(scratch_dir) = self
+                        .status
+                        .get(*name)
+                        .and_then(|s| s.scratch_dir.as_deref())
+                    {
+                        writeln!(writer, "scratch directory retained at {scratch_dir}")?;
+                    }
                     writeln!(writer)?;
                 }
             }
@@ -90,8 +147,74 @@ impl Summary {
             for name in failures.keys() {
                 writeln!(writer, "    {name}")?;
             }
+            writeln!(writer)?;
+            let seed = if let Some(seed) = self.seed {
+                format!(" --seed {seed}")
+            } else {
+                "".to_owned()
+            };
+            let flags = format!("-Zunstable-options --rerun-failed{seed}");
+            match &self.binary {
+                Some(binary) if binary.contains(char::is_whitespace) => {
+                    writeln!(writer, "to rerun the failures, run `\"{binary}\" {flags}`")?;
+                }
+                Some(binary) => {
+                    writeln!(writer, "to rerun the failures, run `{binary} {flags}`")?;
+                }
+                None => {
+                    writeln!(writer, "to rerun the failures, pass `{flags}`")?;
+                }
+            }
+        }
+        if self.report_time {
+            let mut slowest = self
+                .status
+                .iter()
+                .filter(|(_, status)| !status.subcase)
+                .filter_map(|(name, status)| Some((status.duration?, name)))
+                .collect::<Vec<_>>();
+            slowest.sort_by_key(|(duration, name)| (std::cmp::Reverse(*duration), *name));
+            slowest.truncate(SLOWEST);
+            if !slowest.is_empty() {
+                writeln!(writer)?;
+                writeln!(writer, "slowest tests:")?;
+                for (duration, name) in slowest {
+                    writeln!(writer, "    {} {name}", super::Elapsed(duration))?;
+                }
+            }
         }
         writeln!(writer)?;
+        let Counts {
+            passed: num_passed,
+            failed: num_failed,
+            ignored: num_ignored,
+            cancelled: num_cancelled,
+            not_run: num_not_run,
+        } = cases;
+        let cancelled = if 0 < num_cancelled {
+            format!("; {num_cancelled} cancelled")
+        } else {
+            "".to_owned()
+        };
+        let not_run = if 0 < num_not_run {
+            format!("; {num_not_run} not run")
+        } else {
+            "".to_owned()
+        };
+        let num_subcases = subcases.total();
+        let subcases = if 0 < num_subcases {
+            let s = if num_subcases == 1 { "" } else { "s" };
+            let mut counts = format!(
+                "{} passed, {} failed, {} ignored",
+                subcases.passed, subcases.failed, subcases.ignored
+            );
+            if 0 < subcases.cancelled {
+                counts.push_str(&format!(", {} cancelled", subcases.cancelled));
+            }
+            format!("; {num_subcases} sub-case{s} ({counts})")
+        } else {
+            "".to_owned()
+        };
         let finished = if let Some(elapsed_s) = elapsed_s {
             format!("; finished in {elapsed_s}")
         } else {
@@ -100,7 +223,7 @@ impl Summary {
         writeln!(
                     writer,
                     "test result: {summary_style}{summary}{summary_style:#}. {num_passed} passed; {num_failed} failed; {num_ignored} ignored; \
-                        {num_filtered_out} filtered out{finished}",
+                        {num_filtered_out} filtered out{cancelled}{not_run}{subcases}{finished}",
                 )?;
         writeln!(writer)?;
 
@@ -111,18 +234,28 @@ impl Summary {
 impl super::Notifier for Summary {
     fn notify(&mut self, event: Event) -> std::io::Result<()> {
         match event {
-            Event::DiscoverStart(_) => {}
+            Event::DiscoverStart(inner) => {
+                self.binary = inner.binary;
+            }
             Event::DiscoverCase(inner) => {
                 if inner.selected {
                     self.num_run += 1;
+                    if !inner.params.is_empty() {
+                        self.params.insert(inner.name, inner.params);
+                    }
                 } else {
                     self.num_filtered_out += 1;
                 }
             }
             Event::DiscoverComplete(_) => {}
-            Event::RunStart(_) => {}
+            Event::RunStart(inner) => {
+                self.seed = inner.seed;
+            }
             Event::CaseStart(inner) => {
-                self.status.entry(inner.name).or_default().started = true;
+                let status = self.status.entry(inner.name).or_default();
+                status.started = true;
+                status.started_s = inner.elapsed_s;
+                status.subcase = inner.parent.is_some();
             }
             Event::CaseMessage(inner) => {
                 self.status
@@ -132,16 +265,176 @@ impl super::Notifier for Summary {
                     .push(inner);
             }
             Event::CaseComplete(inner) => {
-                self.status.entry(inner.name).or_default().completed = true;
+                let status = self.status.entry(inner.name).or_default();
+                status.completed = true;
+                status.scratch_dir = inner.scratch_dir;
+                if let (Some(started), Some(completed)) = (status.started_s, inner.elapsed_s) {
+                    status.duration = Some(completed.0.saturating_sub(started.0));
+                }
             }
             Event::RunComplete(inner) => {
                 self.elapsed_s = inner.elapsed_s;
+                self.interrupted = inner.interrupted;
             }
         }
         Ok(())
     }
 }
 
+/// Describe a failure, including any structured details
+fn render_message(event: &CaseMessage) -> Option<String> {
+    let mut lines = Vec::new();
+    if let Some(message) = &event.message {
+        lines.push(message.clone());
+    }
+    if let Some(location) = &event.location {
+        lines.push(format!("at {location}"));
+    }
+    for cause in &event.causes {
+        lines.push(format!("caused by: {cause}"));
+    }
+    render_comparison(
+        &mut lines,
+        ("expected", event.expected.as_deref()),
+        ("actual", event.actual.as_deref()),
+    );
+    render_comparison(
+        &mut lines,
+        ("left", event.left.as_deref()),
+        ("right", event.right.as_deref()),
+    );
+    if let Some(backtrace) = &event.backtrace {
+        lines.push("stack backtrace:".to_owned());
+        lines.push(backtrace.clone());
+    }
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
+
+/// Show both sides of a comparison, diffing them by line if either spans multiple lines
+fn render_comparison(
+    lines: &mut Vec<String>,
+    (old_label, old): (&str, Option<&str>),
+    (new_label, new): (&str, Option<&str>),
+) {
+    let diff = match (old, new) {
+        (Some(old), Some(new)) if old.contains('\n') || new.contains('\n') => line_diff(old, new),
+        _ => None,
+    };
+    match diff {
+        Some(diff) => {
+            lines.push(format!(
+                "{FAILED}-{old_label}{FAILED:#} {OK}+{new_label}{OK:#}"
+            ));
+            for (change, line) in diff {
+                let line = match change {
+                    Change::Unchanged => format!(" {line}"),
+                    Change::Removed => format!("{FAILED}-{line}{FAILED:#}"),
+                    Change::Added => format!("{OK}+{line}{OK:#}"),
+                };
+                lines.push(line);
+            }
+        }
+        None => {
+            // Align the values like `assert_eq!` does
+            let width = old_label.len().max(new_label.len());
+            if let Some(old) = old {
+                lines.push(format!("{old_label:>width$}: {old}"));
+            }
+            if let Some(new) = new {
+                lines.push(format!("{new_label:>width$}: {new}"));
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Change {
+    Unchanged,
+    Removed,
+    Added,
+}
+
+/// Lines to remove from `old` and add to get `new`, keeping the longest common subsequence
+///
+/// Returns `None` when the lines that differ are too many to compare.
+fn line_diff<'s>(old: &'s str, new: &'s str) -> Option<Vec<(Change, &'s str)>> {
+    let old = old.lines().collect::<Vec<_>>();
+    let new = new.lines().collect::<Vec<_>>();
+
+    // Only the lines between a common prefix and suffix need comparing
+    let prefix = old
+        .iter()
+        .zip(&new)
+        .take_while(|(old, new)| old == new)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(old, new)| old == new)
+        .count();
+    let (head, old, tail) = (
+        &old[..prefix],
+        &old[prefix..old.len() - suffix],
+        &old[old.len() - suffix..],
+    );
+    let new = &new[prefix..new.len() - suffix];
+    if MAX_DIFF_CELLS < (old.len() + 1).saturating_mul(new.len() + 1) {
+        return None;
+    }
+
+    // `common[i][j]` is the length of the longest common subsequence of `old[i..]` and
+    // `new[j..]`
+    let mut common = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = head
+        .iter()
+        .map(|line| (Change::Unchanged, *line))
+        .collect::<Vec<_>>();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            diff.push((Change::Unchanged, old[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            diff.push((Change::Removed, old[i]));
+            i += 1;
+        } else {
+            diff.push((Change::Added, new[j]));
+            j += 1;
+        }
+    }
+    diff.extend(old[i..].iter().map(|line| (Change::Removed, *line)));
+    diff.extend(new[j..].iter().map(|line| (Change::Added, *line)));
+    diff.extend(tail.iter().map(|line| (Change::Unchanged, *line)));
+    Some(diff)
+}
+
+#[derive(Copy, Clone, Default, Debug)]
+struct Counts {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    cancelled: usize,
+    not_run: usize,
+}
+
+impl Counts {
+    fn total(&self) -> usize {
+        self.passed + self.failed + self.ignored + self.cancelled + self.not_run
+    }
+}
+
 fn find_run_status(case_status: &CaseStatus) -> Option<MessageKind> {
     let mut status = None;
     for event in &case_status.messages {
@@ -155,4 +448,40 @@ struct CaseStatus {
     messages: Vec<CaseMessage>,
     started: bool,
     completed: bool,
+    scratch_dir: Option<String>,
+    started_s: Option<super::Elapsed>,
+    duration: Option<std::time::Duration>,
+    subcase: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn line_diff_keeps_common_lines() {
+        assert_eq!(
+            line_diff("a\nb\nc\nd", "a\nc\nx\nd"),
+            Some(vec![
+                (Change::Unchanged, "a"),
+                (Change::Removed, "b"),
+                (Change::Unchanged, "c"),
+                (Change::Added, "x"),
+                (Change::Unchanged, "d"),
+            ])
+        );
+    }
+
+    #[test]
+    fn line_diff_too_large() {
+        let old = (0..10_000).map(|i| format!("{i}\n")).collect::<String>();
+        let new = (0..10_000)
+            .map(|i| format!("{}\n", i + 1))
+            .collect::<String>();
+        assert_eq!(line_diff(&old, &new), None);
+
+        // Only the differing lines count towards the limit
+        let new = format!("{old}10000\n");
+        assert_eq!(line_diff(&old, &new).map(|diff| diff.len()), Some(10_001));
+    }
 }
@@ -0,0 +1,33 @@
+use super::Event;
+
+/// Send every event to both notifiers
+pub(crate) struct TeeNotifier {
+    primary: Box<dyn super::Notifier>,
+    secondary: Box<dyn super::Notifier>,
+}
+
+impl TeeNotifier {
+    pub(crate) fn new(
+        primary: Box<dyn super::Notifier>,
+        secondary: Box<dyn super::Notifier>,
+    ) -> Self {
+        Self { primary, secondary }
+    }
+}
+
+impl super::Notifier for TeeNotifier {
+    fn threaded(&mut self, yes: bool) {
+        self.primary.threaded(yes);
+        self.secondary.threaded(yes);
+    }
+
+    fn tick(&mut self) -> std::io::Result<()> {
+        self.primary.tick()?;
+        self.secondary.tick()
+    }
+
+    fn notify(&mut self, event: Event) -> std::io::Result<()> {
+        self.secondary.notify(event.clone())?;
+        self.primary.notify(event)
+    }
+}
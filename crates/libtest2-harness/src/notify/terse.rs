@@ -56,6 +56,12 @@ impl<W: std::io::Write> TerseRunNotifier<W> {
             summary: Default::default(),
         }
     }
+
+    /// Report the slowest cases once the run completes
+    pub(crate) fn report_time(mut self, yes: bool) -> Self {
+        self.summary.report_time(yes);
+        self
+    }
 }
 
 impl<W: std::io::Write> super::Notifier for TerseRunNotifier<W> {
@@ -74,6 +80,8 @@ impl<W: std::io::Write> super::Notifier for TerseRunNotifier<W> {
                 let status = self.summary.get_kind(&inner.name);
                 let (c, style) = match status {
                     Some(MessageKind::Ignored) => ('i', IGNORED),
+                    Some(MessageKind::Cancelled) => ('c', IGNORED),
+                    Some(MessageKind::NotRun) => ('n', IGNORED),
                     Some(MessageKind::Error) => ('F', FAILED),
                     None => ('.', OK),
                 };
@@ -0,0 +1,114 @@
+use super::Elapsed;
+use super::Event;
+
+/// Durations of cases from previous runs
+///
+/// Stored as one `<seconds>\t<name>` line per case.
+#[derive(Default, Clone, Debug)]
+pub(crate) struct Timings {
+    cases: std::collections::BTreeMap<String, std::time::Duration>,
+}
+
+impl Timings {
+    /// Read the timings at `path`, treating a missing file as empty
+    pub(crate) fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        let mut cases = std::collections::BTreeMap::new();
+        for (i, line) in content.lines().enumerate() {
+            let parsed = line.split_once('\t').and_then(|(secs, name)| {
+                let secs = secs.parse::<f64>().ok()?;
+                Some((name, std::time::Duration::try_from_secs_f64(secs).ok()?))
+            });
+            let Some((name, duration)) = parsed else {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "{}:{}: expected seconds and a case name, separated by a tab",
+                        path.display(),
+                        i + 1
+                    ),
+                ));
+            };
+            cases.insert(name.to_owned(), duration);
+        }
+        Ok(Self { cases })
+    }
+
+    pub(crate) fn save(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let mut content = String::new();
+        for (name, duration) in &self.cases {
+            content.push_str(&format!("{:.3}\t{name}\n", duration.as_secs_f64()));
+        }
+        std::fs::write(path, content)
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<std::time::Duration> {
+        self.cases.get(name).copied()
+    }
+}
+
+/// Record how long each case takes, saving the timings when the run completes
+pub(crate) struct TimingsNotifier {
+    inner: Box<dyn super::Notifier>,
+    path: std::path::PathBuf,
+    timings: Timings,
+    started: std::collections::HashMap<String, Elapsed>,
+}
+
+impl TimingsNotifier {
+    pub(crate) fn new(
+        inner: Box<dyn super::Notifier>,
+        path: std::path::PathBuf,
+        timings: Timings,
+    ) -> Self {
+        Self {
+            inner,
+            path,
+            timings,
+            started: Default::default(),
+        }
+    }
+}
+
+impl super::Notifier for TimingsNotifier {
+    fn threaded(&mut self, yes: bool) {
+        self.inner.threaded(yes);
+    }
+
+    fn tick(&mut self) -> std::io::Result<()> {
+        self.inner.tick()
+    }
+
+    fn notify(&mut self, event: Event) -> std::io::Result<()> {
+        match &event {
+            Event::CaseStart(inner) if inner.parent.is_none() => {
+                if let Some(elapsed_s) = inner.elapsed_s {
+                    self.started.insert(inner.name.clone(), elapsed_s);
+                }
+            }
+            Event::CaseComplete(inner) => {
+                if let (Some(started), Some(completed)) =
+                    (self.started.remove(&inner.name), inner.elapsed_s)
+                {
+                    let duration = completed.0.saturating_sub(started.0);
+                    self.timings.cases.insert(inner.name.clone(), duration);
+                }
+            }
+            Event::RunComplete(_) => {
+                // The cases have all run by now, so don't fail the run over it
+                if let Err(err) = self.timings.save(&self.path) {
+                    eprintln!(
+                        "warning: failed to save timings to {}: {err}",
+                        self.path.display()
+                    );
+                }
+            }
+            _ => {}
+        }
+        self.inner.notify(event)
+    }
+}
@@ -0,0 +1,226 @@
+use super::Event;
+use super::MessageKind;
+use super::FAILED;
+use super::IGNORED;
+use super::OK;
+
+/// Report results grouped by module path, printing each top-level group once all of its cases
+/// complete
+///
+/// Groups where every case passed are collapsed to their counts.
+#[derive(Debug)]
+pub(crate) struct TreeRunNotifier<W> {
+    writer: W,
+    summary: super::Summary,
+    /// Cases in each top-level group that have yet to complete
+    pending: std::collections::HashMap<String, usize>,
+    /// Completed cases in each top-level group that has yet to be printed
+    completed: std::collections::BTreeMap<String, Vec<String>>,
+}
+
+impl<W: std::io::Write> TreeRunNotifier<W> {
+    pub(crate) fn new(writer: W) -> Self {
+        Self {
+            writer,
+            summary: Default::default(),
+            pending: Default::default(),
+            completed: Default::default(),
+        }
+    }
+
+    /// Report the slowest cases once the run completes
+    pub(crate) fn report_time(mut self, yes: bool) -> Self {
+        self.summary.report_time(yes);
+        self
+    }
+
+    fn write_group(&mut self, group: &str) -> std::io::Result<()> {
+        let Some(names) = self.completed.remove(group) else {
+            return Ok(());
+        };
+        let mut root = Node::default();
+        for name in &names {
+            let status = self.summary.get_kind(name);
+            let node = name.split("::").fold(&mut root, |node, segment| {
+                node.children.entry(segment.to_owned()).or_default()
+            });
+            node.status = Some(status);
+        }
+        for (name, node) in &root.children {
+            self.write_node(name, node, 0)?;
+        }
+        Ok(())
+    }
+
+    fn write_node(&mut self, name: &str, node: &Node, depth: usize) -> std::io::Result<()> {
+        let indent = "    ".repeat(depth);
+        write!(self.writer, "{indent}{name} ... ")?;
+        let mut passed = true;
+        if let Some(status) = node.status {
+            let (s, style) = match status {
+                Some(MessageKind::Ignored) => ("ignored", IGNORED),
+                Some(MessageKind::Cancelled) => ("cancelled", IGNORED),
+                Some(MessageKind::NotRun) => ("not run", IGNORED),
+                Some(MessageKind::Error) => ("FAILED", FAILED),
+                None => ("ok", OK),
+            };
+            write!(self.writer, "{style}{s}{style:#}")?;
+            passed = status.is_none();
+        }
+        if !node.children.is_empty() {
+            let counts = node
+                .children
+                .values()
+                .fold(Counts::default(), |mut counts, child| {
+                    counts.add(child.counts());
+                    counts
+                });
+            if node.status.is_some() {
+                // Sub-cases
+                write!(self.writer, " (")?;
+                counts.write(&mut self.writer)?;
+                write!(self.writer, ")")?;
+            } else {
+                counts.write(&mut self.writer)?;
+            }
+            passed &= counts.passed == counts.total();
+        }
+        writeln!(self.writer)?;
+        if passed {
+            return Ok(());
+        }
+        for (child_name, child) in &node.children {
+            self.write_node(child_name, child, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// The top-level group `name` is reported under
+fn group(name: &str) -> &str {
+    name.split("::").next().unwrap_or(name)
+}
+
+impl<W: std::io::Write> super::Notifier for TreeRunNotifier<W> {
+    fn notify(&mut self, event: Event) -> std::io::Result<()> {
+        self.summary.notify(event.clone())?;
+        match event {
+            Event::DiscoverStart(_) => {}
+            Event::DiscoverCase(inner) => {
+                if inner.selected {
+                    *self
+                        .pending
+                        .entry(group(&inner.name).to_owned())
+                        .or_default() += 1;
+                }
+            }
+            Event::DiscoverComplete(_) => {}
+            Event::RunStart(_) => {
+                self.summary.write_start(&mut self.writer)?;
+            }
+            Event::CaseStart(inner) => {
+                if inner.parent.is_some() {
+                    // Sub-cases start before their parent completes, keeping the group open
+                    *self
+                        .pending
+                        .entry(group(&inner.name).to_owned())
+                        .or_default() += 1;
+                }
+            }
+            Event::CaseMessage(_) => {}
+            Event::CaseComplete(inner) => {
+                let group = group(&inner.name).to_owned();
+                self.completed
+                    .entry(group.clone())
+                    .or_default()
+                    .push(inner.name);
+                let pending = self.pending.entry(group.clone()).or_default();
+                *pending = pending.saturating_sub(1);
+                if *pending == 0 {
+                    self.write_group(&group)?;
+                }
+            }
+            Event::RunComplete(_) => {
+                // Only groups with cases that never reported completing
+                let groups = self.completed.keys().cloned().collect::<Vec<_>>();
+                for group in groups {
+                    self.write_group(&group)?;
+                }
+                self.summary.write_complete(&mut self.writer)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default, Debug)]
+struct Node {
+    /// Status if this is a case, rather than only a module
+    status: Option<Option<MessageKind>>,
+    children: std::collections::BTreeMap<String, Node>,
+}
+
+impl Node {
+    /// Count the cases, leaving out sub-cases like the summary does
+    fn counts(&self) -> Counts {
+        let mut counts = Counts::default();
+        match self.status {
+            Some(Some(MessageKind::Ignored)) => counts.ignored += 1,
+            Some(Some(MessageKind::Cancelled)) => counts.cancelled += 1,
+            Some(Some(MessageKind::NotRun)) => counts.not_run += 1,
+            Some(Some(MessageKind::Error)) => counts.failed += 1,
+            Some(None) => counts.passed += 1,
+            None => {
+                for child in self.children.values() {
+                    counts.add(child.counts());
+                }
+            }
+        }
+        counts
+    }
+}
+
+#[derive(Copy, Clone, Default, Debug)]
+struct Counts {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    cancelled: usize,
+    not_run: usize,
+}
+
+impl Counts {
+    fn add(&mut self, other: Self) {
+        self.passed += other.passed;
+        self.failed += other.failed;
+        self.ignored += other.ignored;
+        self.cancelled += other.cancelled;
+        self.not_run += other.not_run;
+    }
+
+    fn total(&self) -> usize {
+        self.passed + self.failed + self.ignored + self.cancelled + self.not_run
+    }
+
+    fn write(&self, writer: &mut dyn std::io::Write) -> std::io::Result<()> {
+        let parts = [
+            (self.passed, "passed", OK),
+            (self.failed, "failed", FAILED),
+            (self.ignored, "ignored", IGNORED),
+            (self.cancelled, "cancelled", IGNORED),
+            (self.not_run, "not run", IGNORED),
+        ];
+        let mut first = true;
+        for (count, label, style) in parts {
+            if count == 0 {
+                continue;
+            }
+            if !first {
+                write!(writer, "; ")?;
+            }
+            first = false;
+            write!(writer, "{style}{count} {label}{style:#}")?;
+        }
+        Ok(())
+    }
+}
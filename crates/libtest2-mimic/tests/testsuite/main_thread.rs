@@ -29,5 +29,51 @@ fn main() {
 running 1 test
 ...
 
+"#]]);
+}
+
+#[test]
+fn main_thread_cases() {
+    let package_root = crate::util::new_test(
+        r#"
+fn main() {
+    use libtest2_mimic::LocalTrial;
+    use libtest2_mimic::Trial;
+    let main_thread = std::thread::current().id();
+    // Not `Send`
+    let runs = std::rc::Rc::new(std::cell::Cell::new(0));
+    libtest2_mimic::Harness::with_env()
+        .main_thread_cases((0..3).map(|i| {
+            let runs = runs.clone();
+            LocalTrial::test(format!("local::{i}"), move |_| {
+                assert_eq!(main_thread, std::thread::current().id());
+                runs.set(runs.get() + 1);
+                Ok(())
+            })
+        }))
+        .cases((0..3).map(|i| {
+            Trial::test(format!("shared::{i}"), move |_| {
+                assert_ne!(main_thread, std::thread::current().id());
+                Ok(())
+            })
+        }))
+        .main();
+}
+"#,
+        false,
+    );
+    let bin = crate::util::compile_test(&package_root);
+    snapbox::cmd::Command::new(bin)
+        .args(["--test-threads", "4"])
+        .current_dir(package_root)
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 6 tests
+...
+test result: ok. 6 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
 "#]]);
 }
@@ -87,6 +87,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -105,6 +107,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -138,6 +142,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -156,6 +162,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -189,6 +197,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -207,6 +217,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -418,6 +430,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 2 failed; 0 ignored; 4 filtered out; finished in [..]s
 
 
@@ -440,6 +454,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 2 failed; 0 ignored; 4 filtered out; finished in [..]s
 
 
@@ -473,6 +489,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 2 failed; 0 ignored; 4 filtered out; finished in [..]s
 
 
@@ -495,6 +513,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 2 failed; 0 ignored; 4 filtered out; finished in [..]s
 
 
@@ -540,6 +560,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 4 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -570,6 +592,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 4 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -615,6 +639,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 4 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -645,6 +671,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 4 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -672,6 +700,8 @@ broke neck
 failures:
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 1 passed; 1 failed; 0 ignored; 6 filtered out; finished in [..]s
 
 
@@ -690,6 +720,8 @@ broke neck
 failures:
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 1 passed; 1 failed; 0 ignored; 6 filtered out; finished in [..]s
 
 
@@ -707,6 +739,18 @@ fn list_json() {
 [
   {
     "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "--list",
+      "a",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
     "elapsed_s": "[..]"
   },
   {
@@ -767,6 +811,16 @@ fn list_json() {
 [
   {
     "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "--list",
+      "a"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
     "elapsed_s": "[..]"
   },
   {
@@ -836,6 +890,17 @@ fn test_json() {
 [
   {
     "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "a",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
     "elapsed_s": "[..]"
   },
   {
@@ -890,6 +955,8 @@ fn test_json() {
   },
   {
     "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
     "elapsed_s": "[..]"
   },
   {
@@ -939,8 +1006,17 @@ fn test_json() {
     "name": "cat"
   },
   {
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "a"
+    ],
+    "binary": "[..]",
+    "current_dir": "[..]",
     "elapsed_s": "[..]",
-    "event": "discover_start"
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "target": "[..]"
   },
   {
     "elapsed_s": "[..]",
@@ -964,6 +1040,8 @@ fn test_json() {
   },
   {
     "event": "run_start",
+    "seed": "[..]",
+    "threads": "{...}",
     "elapsed_s": "[..]"
   },
   {
@@ -1044,6 +1122,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -1061,6 +1141,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -1080,6 +1162,10 @@ test bear  ... ignored
 test bunny ... ignored
 test cat   ... ok
 test dog   ... FAILED
+test fly   ... not run
+test fox   ... not run
+test frog  ... not run
+test owl   ... not run
 
 failures:
 
@@ -1090,7 +1176,9 @@ was not a good boy
 failures:
     dog
 
-test result: FAILED. 1 passed; 1 failed; 2 ignored; 0 filtered out; finished in [..]s
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 1 failed; 2 ignored; 0 filtered out; 4 not run; finished in [..]s
 
 
 "#]],
@@ -1111,6 +1199,17 @@ fn fail_fast_json() {
 [
   {
     "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "--fail-fast",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
     "elapsed_s": "[..]"
   },
   {
@@ -1159,6 +1258,8 @@ fn fail_fast_json() {
   },
   {
     "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
     "elapsed_s": "[..]"
   },
   {
@@ -1222,6 +1323,50 @@ fn fail_fast_json() {
     "name": "dog",
     "elapsed_s": "[..]"
   },
+  {
+    "event": "case_message",
+    "name": "fly",
+    "kind": "not_run",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "fly",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "fox",
+    "kind": "not_run",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "fox",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "frog",
+    "kind": "not_run",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "frog",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "owl",
+    "kind": "not_run",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "owl",
+    "elapsed_s": "[..]"
+  },
   {
     "event": "run_complete",
     "elapsed_s": "[..]"
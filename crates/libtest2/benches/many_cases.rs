@@ -0,0 +1,88 @@
+//! Run suites of trivial cases to measure the overhead of scheduling them
+//!
+//! Run with `cargo bench -p libtest2 --bench many_cases`
+
+const SIZES: &[usize] = &[100, 1_000, 10_000];
+const THREADS: &str = "4";
+const SAMPLES: usize = 10;
+
+#[allow(clippy::print_stdout)]
+fn main() {
+    // `cargo test --benches` runs this once as a smoke test
+    let smoke = !std::env::args().any(|arg| arg == "--bench");
+
+    let bin = test_bin();
+    println!("{:>8} {:>12} {:>12}", "cases", "fastest", "median");
+    for &cases in SIZES {
+        let samples = if smoke { 1 } else { SAMPLES };
+        let mut times = (0..samples).map(|_| run(&bin, cases)).collect::<Vec<_>>();
+        times.sort();
+        let fastest = times[0];
+        let median = times[times.len() / 2];
+        println!("{cases:>8} {fastest:>12.2?} {median:>12.2?}");
+    }
+}
+
+fn test_bin() -> std::path::PathBuf {
+    compile(
+        r#"
+fn main() {
+    let cases = std::env::var("CASES").unwrap().parse::<usize>().unwrap();
+    libtest2::Harness::with_env()
+        .cases((0..cases).map(|i| libtest2::Trial::test(format!("case_{i}"), |_| Ok(()))))
+        .main();
+}
+"#,
+    )
+}
+
+/// Build `main` as a binary depending on this `libtest2`
+fn compile(main: &str) -> std::path::PathBuf {
+    let package_root = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("many_cases");
+    std::fs::create_dir_all(&package_root).unwrap();
+    let libtest2 = env!("CARGO_MANIFEST_DIR").replace('\\', "/");
+    std::fs::write(
+        package_root.join("Cargo.toml"),
+        format!(
+            r#"
+[workspace]
+
+[package]
+name = "many_cases"
+version = "0.0.0"
+edition = "2021"
+
+[dependencies]
+libtest2.path = "{libtest2}"
+
+[[bin]]
+name = "many_cases"
+path = "main.rs"
+"#
+        ),
+    )
+    .unwrap();
+    std::fs::write(package_root.join("main.rs"), main).unwrap();
+
+    escargot::CargoBuild::new()
+        .bin("many_cases")
+        .release()
+        .manifest_path(package_root.join("Cargo.toml"))
+        .target_dir(package_root.join("target"))
+        .run()
+        .unwrap_or_else(|e| panic!("{e}"))
+        .path()
+        .to_owned()
+}
+
+fn run(bin: &std::path::Path, cases: usize) -> std::time::Duration {
+    let start = std::time::Instant::now();
+    let output = std::process::Command::new(bin)
+        .args(["--quiet", "--test-threads", THREADS])
+        .env("CASES", cases.to_string())
+        .output()
+        .unwrap();
+    let elapsed = start.elapsed();
+    assert!(output.status.success(), "{output:?}");
+    elapsed
+}
@@ -19,6 +19,17 @@
 //!     .main();
 //! ```
 //!
+//! # Ctrl-C
+//!
+//! By default, Ctrl-C kills the test binary mid-run, without a summary and cutting off any
+//! `--format json` output.  Enable the `signals` feature to instead let running cases wind down
+//! and report the run as usual, see [`Harness::interrupt_grace_period`]:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! libtest2 = { version = "*", features = ["signals"] }
+//! ```
+//!
 
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 #![warn(clippy::print_stderr)]
@@ -35,6 +46,11 @@ use libtest2_harness::Source;
 
 pub struct Trial {
     name: String,
+    params: Vec<(String, String)>,
+    kind: TestKind,
+    source: Option<Source>,
+    ignored: bool,
+    tags: Vec<String>,
     #[allow(clippy::type_complexity)]
     runner: Box<dyn Fn(&TestContext) -> Result<(), RunError> + Send + Sync>,
 }
@@ -46,12 +62,219 @@ impl Trial {
     ) -> Self {
         Self {
             name: name.into(),
+            params: Vec::new(),
+            kind: Default::default(),
+            source: None,
+            ignored: false,
+            tags: Vec::new(),
             runner: Box::new(runner),
         }
     }
+
+    /// Where the test comes from, for selecting tests with `--filter-expr`
+    pub fn with_kind(mut self, kind: TestKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// File the test is defined in, for selecting tests with `--filter-expr`
+    pub fn with_source_file(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.source = Some(Source::Path(path.into()));
+        self
+    }
+
+    /// Only run the test with `--ignored` or `--include-ignored`
+    pub fn with_ignored_flag(mut self, ignored: bool) -> Self {
+        self.ignored = ignored;
+        self
+    }
+
+    /// Categorize the test, for selecting tests with `--tag` and `--skip-tag`
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+        self
+    }
+
+    /// Generate a case for each parameter set, named `<name>::<label>[::<label>...]`
+    ///
+    /// ```
+    /// # use libtest2::*;
+    /// let trials = Trial::parametrize(
+    ///     "parse",
+    ///     Params::new("input", [("empty", ""), ("ascii", "hello")])
+    ///         .product("radix", [(2, 2), (10, 10)]),
+    ///     |_context, (input, radix)| {
+    ///         let _ = u32::from_str_radix(input, *radix);
+    ///         Ok(())
+    ///     },
+    /// );
+    /// assert_eq!(trials.len(), 4);
+    /// ```
+    pub fn parametrize<P: Send + Sync + 'static>(
+        name: impl std::fmt::Display,
+        params: Params<P>,
+        runner: impl Fn(&TestContext, &P) -> Result<(), RunError> + Send + Sync + 'static,
+    ) -> Vec<Self> {
+        let runner = std::sync::Arc::new(runner);
+        params
+            .sets
+            .into_iter()
+            .map(|(labels, value)| {
+                let mut case_name = name.to_string();
+                for (_, label) in &labels {
+                    case_name.push_str("::");
+                    case_name.push_str(label);
+                }
+                let runner = runner.clone();
+                let mut trial = Self::test(case_name, move |context| runner(context, &value));
+                trial.params = labels;
+                trial
+            })
+            .collect()
+    }
+
+    /// Run an `async fn` with [`TestContext::block_on`]
+    ///
+    /// See [`Harness::executor`] for selecting the runtime.
+    pub fn test_async(
+        name: impl Into<String>,
+        runner: impl for<'c> AsyncRunner<'c> + Send + Sync + 'static,
+    ) -> Self {
+        Self::test(name, move |context| context.block_on(runner.call(context)))
+    }
+}
+
+/// A function returning a future that borrows the [`TestContext`], like an `async fn`
+pub trait AsyncRunner<'c> {
+    type Future: std::future::Future<Output = RunResult> + 'c;
+
+    fn call(&self, context: &'c TestContext) -> Self::Future;
+}
+
+impl<'c, F, Fut> AsyncRunner<'c> for F
+where
+    F: Fn(&'c TestContext) -> Fut,
+    Fut: std::future::Future<Output = RunResult> + 'c,
+{
+    type Future = Fut;
+
+    fn call(&self, context: &'c TestContext) -> Self::Future {
+        self(context)
+    }
+}
+
+/// Named parameter sets for [`Trial::parametrize`]
+pub struct Params<P> {
+    sets: Vec<(Vec<(String, String)>, P)>,
+}
+
+impl<P> Params<P> {
+    /// One parameter set per value, with `label` used in the case name
+    ///
+    /// # Panics
+    ///
+    /// If a label is empty, contains whitespace or `::`, or is repeated, as the case names would
+    /// be ambiguous.
+    pub fn new(
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = (impl std::fmt::Display, P)>,
+    ) -> Self {
+        let name = name.into();
+        let mut seen = std::collections::HashSet::new();
+        let sets = values
+            .into_iter()
+            .map(|(label, value)| {
+                let label = label.to_string();
+                if label.is_empty() || label.contains("::") || label.contains(char::is_whitespace) {
+                    panic!(
+                        "parameter `{name}` has the invalid label `{label}`, \
+                        labels must be non-empty without whitespace or `::`"
+                    );
+                }
+                if !seen.insert(label.clone()) {
+                    panic!("parameter `{name}` has the label `{label}` more than once");
+                }
+                (vec![(name.clone(), label)], value)
+            })
+            .collect();
+        Self { sets }
+    }
+
+    /// Combine every existing parameter set with every one of `values`
+    pub fn product<Q: Clone>(
+        self,
+        name: impl Into<String>,
+        values: impl IntoIterator<Item = (impl std::fmt::Display, Q)>,
+    ) -> Params<(P, Q)>
+    where
+        P: Clone,
+    {
+        let other = Params::new(name, values);
+        let mut sets = Vec::with_capacity(self.sets.len() * other.sets.len());
+        for (labels, value) in &self.sets {
+            for (other_labels, other_value) in &other.sets {
+                let mut labels = labels.clone();
+                labels.extend(other_labels.iter().cloned());
+                sets.push((labels, (value.clone(), other_value.clone())));
+            }
+        }
+        Params { sets }
+    }
 }
 
 impl Case for Trial {
+    fn name(&self) -> &str {
+        &self.name
+    }
+    fn kind(&self) -> TestKind {
+        self.kind
+    }
+    fn source(&self) -> Option<&Source> {
+        self.source.as_ref()
+    }
+    fn exclusive(&self, _: &TestContext) -> bool {
+        false
+    }
+    fn ignored(&self) -> bool {
+        self.ignored
+    }
+    fn params(&self) -> &[(String, String)] {
+        &self.params
+    }
+    fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    fn run(&self, context: &TestContext) -> Result<(), RunError> {
+        (self.runner)(context)
+    }
+}
+
+/// A [`Trial`] that need not be `Send` or `Sync`
+///
+/// Register these with [`Harness::main_thread_case`] to run them on the main thread.
+pub struct LocalTrial {
+    name: String,
+    #[allow(clippy::type_complexity)]
+    runner: Box<dyn Fn(&TestContext) -> Result<(), RunError>>,
+}
+
+impl LocalTrial {
+    pub fn test(
+        name: impl Into<String>,
+        runner: impl Fn(&TestContext) -> Result<(), RunError> + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            runner: Box::new(runner),
+        }
+    }
+}
+
+impl Case for LocalTrial {
     fn name(&self) -> &str {
         &self.name
     }
@@ -0,0 +1,251 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::RunResult;
+use libtest2::TestContext;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test_async("passes", passes),
+            Trial::test_async("woken_by_thread", woken_by_thread),
+            Trial::test_async("fails", fails),
+            Trial::test_async("panics", panics),
+            Trial::test_async("ignored", ignored),
+        ])
+        .main();
+}
+
+async fn passes(_context: &TestContext) -> RunResult {
+    YieldOnce(false).await;
+    Ok(())
+}
+
+async fn woken_by_thread(_context: &TestContext) -> RunResult {
+    let value = Delayed::new(std::time::Duration::from_millis(10)).await;
+    assert_eq!(value, 42);
+    Ok(())
+}
+
+async fn fails(_context: &TestContext) -> RunResult {
+    YieldOnce(false).await;
+    Err(RunError::fail("no luck"))
+}
+
+async fn panics(_context: &TestContext) -> RunResult {
+    YieldOnce(false).await;
+    panic!("uh oh")
+}
+
+async fn ignored(context: &TestContext) -> RunResult {
+    YieldOnce(false).await;
+    context.ignore_for("slow")?;
+    Ok(())
+}
+
+struct YieldOnce(bool);
+
+impl std::future::Future for YieldOnce {
+    type Output = ();
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<()> {
+        if self.0 {
+            std::task::Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            std::task::Poll::Pending
+        }
+    }
+}
+
+struct Delayed {
+    delay: std::time::Duration,
+    state: std::sync::Arc<std::sync::Mutex<Option<u32>>>,
+    started: bool,
+}
+
+impl Delayed {
+    fn new(delay: std::time::Duration) -> Self {
+        Self {
+            delay,
+            state: Default::default(),
+            started: false,
+        }
+    }
+}
+
+impl std::future::Future for Delayed {
+    type Output = u32;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<u32> {
+        if let Some(value) = *self.state.lock().unwrap() {
+            return std::task::Poll::Ready(value);
+        }
+        if !self.started {
+            self.started = true;
+            let delay = self.delay;
+            let state = self.state.clone();
+            let waker = cx.waker().clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(delay);
+                *state.lock().unwrap() = Some(42);
+                waker.wake();
+            });
+        }
+        std::task::Poll::Pending
+    }
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+        .current_dir(current_dir)
+        .env_remove("RUST_BACKTRACE")
+}
+
+fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
+    test_cmd()
+        .args(args)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(code)
+        .stdout_eq(single);
+    test_cmd()
+        .args(args)
+        .assert()
+        .code(code)
+        .stdout_eq(parallel);
+}
+
+#[test]
+fn normal() {
+    check(
+        &[],
+        101,
+        str![[r#"
+
+running 5 tests
+test fails           ... FAILED
+test ignored         ... ignored
+test panics          ... FAILED
+test passes          ... ok
+test woken_by_thread ... ok
+
+failures:
+
+---- fails ----
+no luck
+
+---- panics ----
+test panicked: uh oh
+at test.rs:37:5
+
+
+failures:
+    fails
+    panics
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 2 passed; 2 failed; 1 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 5 tests
+...
+
+failures:
+
+---- fails ----
+no luck
+
+---- panics ----
+test panicked: uh oh
+at test.rs:37:5
+
+
+failures:
+    fails
+    panics
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 2 passed; 2 failed; 1 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn custom_executor() {
+    let package_root = crate::util::new_test(
+        r#"
+use libtest2::RunResult;
+use libtest2::TestContext;
+use libtest2::Trial;
+
+thread_local! {
+    static IN_EXECUTOR: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+fn main() {
+    libtest2::Harness::with_env()
+        .executor(|mut future| {
+            IN_EXECUTOR.with(|e| e.set(true));
+            let mut cx = std::task::Context::from_waker(std::task::Waker::noop());
+            let outcome = loop {
+                if let std::task::Poll::Ready(outcome) = future.as_mut().poll(&mut cx) {
+                    break outcome;
+                }
+                std::thread::yield_now();
+            };
+            IN_EXECUTOR.with(|e| e.set(false));
+            outcome
+        })
+        .cases(vec![Trial::test_async("check", check)])
+        .main();
+}
+
+async fn check(_context: &TestContext) -> RunResult {
+    assert!(IN_EXECUTOR.with(|e| e.get()));
+    Ok(())
+}
+"#,
+        false,
+    );
+    let bin = crate::util::compile_test(&package_root);
+    snapbox::cmd::Command::new(bin)
+        .current_dir(package_root)
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 1 test
+test check ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
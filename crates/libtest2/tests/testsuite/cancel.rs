@@ -0,0 +1,107 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("fails", |_context| {
+                std::thread::sleep(std::time::Duration::from_millis(100));
+                Err(RunError::fail("no luck"))
+            }),
+            Trial::test("waits", |context| {
+                assert!(!context.is_cancelled());
+                if !context.wait_for_cancellation(std::time::Duration::from_secs(10)) {
+                    return Err(RunError::fail("never cancelled"));
+                }
+                context.check_cancelled()?;
+                Err(RunError::fail("should have stopped"))
+            }),
+            Trial::test("records", |context| {
+                context.record_error(RunError::fail("recorded before cancelling"));
+                context.wait_for_cancellation(std::time::Duration::from_secs(10));
+                context.check_cancelled()?;
+                Err(RunError::fail("should have stopped"))
+            }),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn fail_fast() {
+    test_cmd()
+        .args(["--fail-fast", "--test-threads", "3"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 3 tests
+test fails   ... FAILED
+...
+
+failures:
+
+---- fails ----
+no luck
+
+---- records ----
+recorded before cancelling
+
+
+failures:
+    fails
+    records
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 0 passed; 2 failed; 0 ignored; 0 filtered out; 1 cancelled; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn fail_fast_terse() {
+    test_cmd()
+        .args(["--fail-fast", "--test-threads", "3", "--quiet"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 3 tests
+F[..]
+failures:
+
+---- fails ----
+no luck
+
+---- records ----
+recorded before cancelling
+
+
+failures:
+    fails
+    records
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 0 passed; 2 failed; 0 ignored; 0 filtered out; 1 cancelled; finished in [..]s
+
+
+"#]]);
+}
@@ -0,0 +1,99 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("cat", |_context| Ok(())),
+            Trial::test("dog", |_context| Ok(())),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--format=json",
+            "--test-threads=3",
+            "--seed=42",
+            "cat",
+        ])
+        .assert()
+        .success()
+        .stdout_eq(
+            str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "--test-threads=3",
+      "--seed=42",
+      "cat"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "dog",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "cat",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "42",
+    "threads": 1,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "cat",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "cat",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        );
+}
@@ -0,0 +1,278 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+#[derive(Debug)]
+struct ConfigError(std::num::ParseIntError);
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("invalid config")
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("assert", |_context| {
+                assert_eq!(2 + 2, 5);
+                Ok(())
+            }),
+            Trial::test("chain", |_context| {
+                let err = "four".parse::<u32>().unwrap_err();
+                Err(ConfigError(err).into())
+            }),
+            Trial::test("diff", |_context| {
+                Err(RunError::fail("output mismatch")
+                    .with_location(std::panic::Location::caller())
+                    .with_expected_actual("one\ntwo\nthree", "one\n2\nthree\nfour"))
+            }),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+        .env_remove("RUST_BACKTRACE")
+        .args(["--test-threads=1"])
+}
+
+#[test]
+fn normal() {
+    test_cmd().assert().code(101).stdout_eq(str![[r#"
+
+running 3 tests
+test assert ... FAILED
+test chain  ... FAILED
+test diff   ... FAILED
+
+failures:
+
+---- assert ----
+test panicked: assertion `left == right` failed
+at test.rs:24:17
+ left: 4
+right: 5
+
+---- chain ----
+invalid config
+caused by: invalid digit found in string
+
+---- diff ----
+output mismatch
+at test.rs:33:36
+-expected +actual
+ one
+-two
++2
+ three
++four
+
+
+failures:
+    assert
+    chain
+    diff
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 0 passed; 3 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    test_cmd()
+        .args(["-Zunstable-options", "--format=json"])
+        .assert()
+        .code(101)
+        .stdout_eq(
+            str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "--test-threads=1",
+      "-Zunstable-options",
+      "--format=json"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "assert",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "chain",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "diff",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "assert",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "assert",
+    "kind": "error",
+    "message": "test panicked: assertion `left == right` failed",
+    "location": "test.rs:24:17",
+    "left": "4",
+    "right": "5",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "assert",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "chain",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "chain",
+    "kind": "error",
+    "message": "invalid config",
+    "causes": [
+      "invalid digit found in string"
+    ],
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "chain",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "diff",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "diff",
+    "kind": "error",
+    "message": "output mismatch",
+    "location": "test.rs:33:36",
+    "expected": "one\ntwo\nthree",
+    "actual": "one\n2\nthree\nfour",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "diff",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        );
+}
+
+#[test]
+fn backtrace() {
+    test_cmd()
+        .env("RUST_BACKTRACE", "1")
+        .args(["assert"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 1 test
+test assert ... FAILED
+
+failures:
+
+---- assert ----
+test panicked: assertion `left == right` failed
+at test.rs:24:17
+ left: 4
+right: 5
+stack backtrace:
+...
+[..]: [..]::main::{{closure}}
+             at [..]test.rs:24:17
+...
+
+
+failures:
+    assert
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 0 passed; 1 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn backtrace_json() {
+    let output = test_cmd()
+        .env("RUST_BACKTRACE", "1")
+        .args(["-Zunstable-options", "--format=json", "assert"])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let message = stdout
+        .lines()
+        .find(|line| line.contains(r#""event":"case_message""#))
+        .unwrap();
+    assert!(message.contains(r#""backtrace":""#), "{message}");
+    assert!(message.contains("main::{{closure}}"), "{message}");
+    // Trimmed to the frames within the case
+    assert!(
+        !message.contains("__rust_begin_short_backtrace"),
+        "{message}"
+    );
+}
@@ -0,0 +1,146 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::TestKind;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("parser::expr::fast_add", |_context| Ok(()))
+                .with_kind(TestKind::UnitTest)
+                .with_source_file("src/parser/expr.rs"),
+            Trial::test("parser::expr::slow_deep", |_context| Ok(()))
+                .with_kind(TestKind::UnitTest)
+                .with_source_file("src/parser/expr.rs"),
+            Trial::test("parser::stmt::let_else", |_context| Ok(()))
+                .with_kind(TestKind::UnitTest)
+                .with_source_file("src/parser/stmt.rs")
+                .with_ignored_flag(true),
+            Trial::test("cli::help", |_context| Ok(()))
+                .with_kind(TestKind::IntegrationTest)
+                .with_source_file("tests/cli.rs"),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+    snapbox::cmd::Command::new(bin).args(["-Zunstable-options"])
+}
+
+fn check(exprs: &[&str], expected: impl snapbox::data::IntoData) {
+    let mut cmd = test_cmd().args(["--list"]);
+    for expr in exprs {
+        cmd = cmd.args(["--filter-expr", expr]);
+    }
+    cmd.assert().success().stdout_eq(expected);
+}
+
+#[test]
+fn module() {
+    check(
+        &["module(parser::expr)"],
+        str![[r#"
+parser::expr::fast_add: test
+parser::expr::slow_deep: test
+
+2 tests
+
+
+"#]],
+    );
+}
+
+#[test]
+fn name_and_not() {
+    check(
+        &["module(parser) and not (name(*::slow_*) or ignored)"],
+        str![[r#"
+parser::expr::fast_add: test
+
+1 tests
+
+
+"#]],
+    );
+}
+
+#[test]
+fn kind_and_source() {
+    check(
+        &["kind(integration) | source(src/*/stmt.rs)"],
+        str![[r#"
+cli::help: test
+parser::stmt::let_else: test
+
+2 tests
+
+
+"#]],
+    );
+}
+
+#[test]
+fn any_of_several() {
+    check(
+        &["name(cli::*)", "ignored"],
+        str![[r#"
+cli::help: test
+parser::stmt::let_else: test
+
+2 tests
+
+
+"#]],
+    );
+}
+
+#[test]
+fn ignored_flag() {
+    test_cmd()
+        .args(["--filter-expr", "module(parser::stmt)", "--include-ignored"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 1 test
+test parser::stmt::let_else ... ok
+
+test result: ok. 1 passed; 0 failed; 0 ignored; 3 filtered out; finished in [..]s
+
+
+"#]]);
+    test_cmd()
+        .args(["--filter-expr", "module(parser::stmt)"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 1 test
+test parser::stmt::let_else ... ignored
+
+test result: ok. 0 passed; 0 failed; 1 ignored; 3 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn invalid() {
+    test_cmd()
+        .args(["--list", "--filter-expr", "module(parser) and kind(bench)"])
+        .assert()
+        .code(1)
+        .stderr_eq(str![[r#"
+unknown test kind `bench`, expected one of `unit`, `integration`, `doc`, `unknown` at column 20, found `module(parser) and kind(bench)` when parsing `--filter-expr`
+
+"#]]);
+}
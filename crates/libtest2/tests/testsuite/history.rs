@@ -0,0 +1,161 @@
+use snapbox::str;
+
+fn test_bin() -> &'static (std::path::PathBuf, std::path::PathBuf) {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("broken", |_context| Err(RunError::fail("no luck"))),
+            Trial::test("flaky", |_context| {
+                if std::path::Path::new("fail").exists() {
+                    return Err(RunError::fail("no luck this time"));
+                }
+                Ok(())
+            }),
+            Trial::test("steady", |_context| Ok(())),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    })
+}
+
+/// Create a fresh directory to run in
+fn fresh_dir(name: &str) -> std::path::PathBuf {
+    let (_, package_root) = test_bin();
+    let dir = package_root.join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn test_cmd(dir: &std::path::Path) -> snapbox::cmd::Command {
+    let (bin, _) = test_bin();
+    snapbox::cmd::Command::new(bin).current_dir(dir).args([
+        "-Zunstable-options",
+        "--history",
+        "history.txt",
+    ])
+}
+
+/// The events of a run where each case takes the given number of seconds
+fn run_events(cases: &[(&str, Option<&str>, f64)]) -> String {
+    let mut events = String::new();
+    events.push_str(r#"{"event":"run_start","elapsed_s":"0.000"}"#);
+    events.push('\n');
+    let mut elapsed_s = 0.0;
+    for (name, kind, secs) in cases {
+        events.push_str(&format!(
+            r#"{{"event":"case_start","name":"{name}","elapsed_s":"{elapsed_s:.3}"}}"#
+        ));
+        events.push('\n');
+        elapsed_s += secs;
+        if let Some(kind) = kind {
+            events.push_str(&format!(
+                r#"{{"event":"case_message","name":"{name}","kind":"{kind}","elapsed_s":"{elapsed_s:.3}"}}"#
+            ));
+            events.push('\n');
+        }
+        events.push_str(&format!(
+            r#"{{"event":"case_complete","name":"{name}","elapsed_s":"{elapsed_s:.3}"}}"#
+        ));
+        events.push('\n');
+    }
+    events.push_str(&format!(
+        r#"{{"event":"run_complete","elapsed_s":"{elapsed_s:.3}"}}"#
+    ));
+    events.push('\n');
+    events
+}
+
+#[test]
+fn records_history() {
+    let dir = fresh_dir("records_history");
+    test_cmd(&dir).assert().code(101);
+    std::fs::write(dir.join("fail"), "").unwrap();
+    test_cmd(&dir).args(["flaky"]).assert().code(101);
+
+    // The events of each run, as with `--format json`
+    let history = std::fs::read_to_string(dir.join("history.txt")).unwrap();
+    let runs = history
+        .lines()
+        .filter(|line| line.starts_with(r#"{"event":"run_complete""#))
+        .count();
+    assert_eq!(runs, 2, "{history}");
+
+    test_cmd(&dir)
+        .args(["--list"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+broken: 0/1 passed; 0 recent flips F; [..]s mean
+flaky: 1/2 passed; 1 recent flip .F; [..]s mean, [..]s per run
+steady: 1/1 passed; 0 recent flips .; [..]s mean
+
+3 tests
+
+
+"#]]);
+}
+
+#[test]
+fn report() {
+    let dir = fresh_dir("report");
+    let history = [
+        run_events(&[("broken", Some("error"), 0.1), ("flaky", None, 0.1)]),
+        run_events(&[
+            ("broken", Some("error"), 0.1),
+            ("flaky", Some("error"), 0.2),
+        ]),
+        run_events(&[
+            ("broken", Some("error"), 0.1),
+            ("flaky", Some("ignored"), 0.0),
+        ]),
+        run_events(&[("broken", Some("error"), 0.1), ("flaky", None, 0.6)]),
+    ]
+    .concat();
+    std::fs::write(dir.join("history.txt"), history).unwrap();
+
+    test_cmd(&dir)
+        .args(["--list"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+broken: 0/4 passed; 0 recent flips FFFF; 0.100s mean, +0.000s per run
+flaky: 2/3 passed; 2 recent flips .Fi.; 0.300s mean, +0.250s per run
+steady: no history
+
+3 tests
+
+
+"#]]);
+}
+
+#[test]
+fn malformed() {
+    let dir = fresh_dir("malformed");
+    // The format before history kept the events
+    let mut history = run_events(&[("broken", Some("error"), 0.1)]);
+    history.push_str("run\t1700000000\n");
+    std::fs::write(dir.join("history.txt"), history).unwrap();
+
+    test_cmd(&dir)
+        .args(["--list"])
+        .assert()
+        .code(1)
+        .stderr_eq(str![[r#"
+history.txt:6: expected a value at column 1
+
+"#]]);
+}
@@ -0,0 +1,347 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .before_each(|name, _context| {
+            if name == "blocked" {
+                Err(libtest2::RunError::fail("not allowed to run"))
+            } else {
+                Ok(())
+            }
+        })
+        .after_each(|name, _context| {
+            if name.starts_with("leaky") {
+                panic!("left a mess behind");
+            }
+            Ok(())
+        })
+        .cases(vec![
+            Trial::test("passes", |_| Ok(())),
+            Trial::test("blocked", |_| panic!("should never run")),
+            Trial::test("leaky", |_| Ok(())),
+            Trial::test("leaky_and_failed", |_| Err(libtest2::RunError::fail("oops"))),
+            Trial::test("leaky_and_ignored", |context| {
+                context.ignore()?;
+                Ok(())
+            }),
+            Trial::test("leaky_but_skipped", |_| Ok(())).with_ignored_flag(true),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+        .current_dir(current_dir)
+        .env_remove("RUST_BACKTRACE")
+}
+
+fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
+    test_cmd()
+        .args(args)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(code)
+        .stdout_eq(single);
+    test_cmd()
+        .args(args)
+        .assert()
+        .code(code)
+        .stdout_eq(parallel);
+}
+
+#[test]
+fn normal() {
+    check(
+        &[],
+        101,
+        str![[r#"
+
+running 6 tests
+test blocked           ... FAILED
+test leaky             ... FAILED
+test leaky_and_failed  ... FAILED
+test leaky_and_ignored ... FAILED
+test leaky_but_skipped ... ignored
+test passes            ... ok
+
+failures:
+
+---- blocked ----
+not allowed to run
+
+---- leaky ----
+test panicked: left a mess behind
+at test.rs:15:17
+
+---- leaky_and_failed ----
+oops
+
+test panicked: left a mess behind
+at test.rs:15:17
+
+---- leaky_and_ignored ----
+test panicked: left a mess behind
+at test.rs:15:17
+
+
+failures:
+    blocked
+    leaky
+    leaky_and_failed
+    leaky_and_ignored
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 4 failed; 1 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 6 tests
+...
+
+failures:
+
+---- blocked ----
+not allowed to run
+
+---- leaky ----
+test panicked: left a mess behind
+at test.rs:15:17
+
+---- leaky_and_failed ----
+oops
+
+test panicked: left a mess behind
+at test.rs:15:17
+
+---- leaky_and_ignored ----
+test panicked: left a mess behind
+at test.rs:15:17
+
+
+failures:
+    blocked
+    leaky
+    leaky_and_failed
+    leaky_and_ignored
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 4 failed; 1 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    check(
+        &["-Zunstable-options", "--format=json", "leaky_and_failed"],
+        101,
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "leaky_and_failed",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "blocked",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "leaky",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "leaky_and_ignored",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "leaky_but_skipped",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "passes",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "leaky_and_failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "leaky_and_failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "leaky_and_failed",
+    "kind": "error",
+    "message": "oops",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "leaky_and_failed",
+    "kind": "error",
+    "message": "test panicked: left a mess behind",
+    "location": "test.rs:15:17",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "leaky_and_failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "leaky_and_failed"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "blocked",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "leaky",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "leaky_and_ignored",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "leaky_but_skipped",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "passes",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "leaky_and_failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": "{...}",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "leaky_and_failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "leaky_and_failed",
+    "kind": "error",
+    "message": "oops",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "leaky_and_failed",
+    "kind": "error",
+    "message": "test panicked: left a mess behind",
+    "location": "test.rs:15:17",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "leaky_and_failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+    );
+}
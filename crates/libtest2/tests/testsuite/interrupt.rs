@@ -0,0 +1,134 @@
+#![cfg(unix)]
+
+use std::io::Read as _;
+
+use snapbox::assert_data_eq;
+use snapbox::str;
+
+fn test_bin() -> &'static std::path::PathBuf {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    BIN.get_or_init(|| {
+        let package_root = crate::util::new_test_with_features(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .interrupt_grace_period(std::time::Duration::from_millis(500))
+        .cases(vec![
+            Trial::test("fast", |_context| Ok(())),
+            Trial::test("slow", |context| {
+                if !context.wait_for_cancellation(std::time::Duration::from_secs(30)) {
+                    return Err(RunError::fail("never interrupted"));
+                }
+                context.check_cancelled()?;
+                Err(RunError::fail("should have stopped"))
+            }),
+            Trial::test("stuck", |_context| {
+                std::thread::sleep(std::time::Duration::from_secs(30));
+                Ok(())
+            }),
+        ])
+        .main();
+}
+"#,
+            false,
+            &["signals"],
+        );
+        crate::util::compile_test(&package_root)
+    })
+}
+
+/// Run the tests, sending `SIGINT` once `until` is printed
+fn interrupt(args: &[&str], until: &str) -> (Option<i32>, String) {
+    let mut child = std::process::Command::new(test_bin())
+        .args(args)
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = child.stdout.take().unwrap();
+    let mut output = String::new();
+    while !output.contains(until) {
+        // Not by line as a case's line is only finished once it completes
+        let mut buffer = [0; 256];
+        let read = stdout.read(&mut buffer).unwrap();
+        assert_ne!(read, 0, "exited before printing {until:?}:\n{output}");
+        output.push_str(std::str::from_utf8(&buffer[..read]).unwrap());
+    }
+    let status = std::process::Command::new("kill")
+        .args(["-INT", &child.id().to_string()])
+        .status()
+        .unwrap();
+    assert!(status.success());
+    stdout.read_to_string(&mut output).unwrap();
+    let status = child.wait().unwrap();
+    (status.code(), output)
+}
+
+#[test]
+fn winds_down() {
+    let (code, output) = interrupt(&["--test-threads", "3"], "test fast  ... ok\n");
+    assert_eq!(code, Some(101));
+    assert_data_eq!(
+        output,
+        str![[r#"
+
+running 3 tests
+test fast  ... ok
+test slow  ... cancelled
+test stuck ... cancelled
+
+test result: INTERRUPTED. 1 passed; 0 failed; 0 ignored; 0 filtered out; 2 cancelled; finished in [..]s
+
+
+"#]]
+    );
+}
+
+#[test]
+fn winds_down_single_threaded() {
+    let (code, output) = interrupt(&["--test-threads", "1"], "test slow  ... ");
+    assert_eq!(code, Some(101));
+    assert_data_eq!(
+        output,
+        str![[r#"
+
+running 3 tests
+test fast  ... ok
+test slow  ... cancelled
+test stuck ... not run
+
+test result: INTERRUPTED. 1 passed; 0 failed; 0 ignored; 0 filtered out; 1 cancelled; 1 not run; finished in [..]s
+
+
+"#]]
+    );
+}
+
+#[test]
+fn grace_period_single_threaded() {
+    // Don't count building it
+    test_bin();
+    let start = std::time::Instant::now();
+    let (code, output) = interrupt(&["--test-threads", "1", "fast", "stuck"], "test stuck ... ");
+    assert!(
+        start.elapsed() < std::time::Duration::from_secs(10),
+        "`stuck` outlived its grace period"
+    );
+    assert_eq!(code, Some(101));
+    assert_data_eq!(
+        output,
+        str![[r#"
+
+running 2 tests
+test fast  ... ok
+test stuck ... cancelled
+
+test result: INTERRUPTED. 1 passed; 0 failed; 0 ignored; 1 filtered out; 1 cancelled; finished in [..]s
+
+
+"#]]
+    );
+}
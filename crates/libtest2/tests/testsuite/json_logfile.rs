@@ -0,0 +1,142 @@
+#![cfg(feature = "json")]
+
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_bin() -> &'static (std::path::PathBuf, std::path::PathBuf) {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("broken", |_context| Err(RunError::fail("no luck"))),
+            Trial::test("fine", |_context| Ok(())),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    })
+}
+
+#[test]
+fn json_logfile() {
+    let (bin, current_dir) = test_bin();
+    let logfile = current_dir.join("events.jsonl");
+    let _ = std::fs::remove_file(&logfile);
+
+    snapbox::cmd::Command::new(bin)
+        .current_dir(current_dir)
+        .args([
+            "-Zunstable-options",
+            "--json-logfile",
+            "events.jsonl",
+            "--test-threads=1",
+            "--quiet",
+        ])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+F.
+failures:
+
+---- broken ----
+no luck
+
+
+failures:
+    broken
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(&logfile).unwrap(),
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--json-logfile",
+      "events.jsonl",
+      "--test-threads=1",
+      "--quiet"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "broken",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "fine",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "broken",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "broken",
+    "kind": "error",
+    "message": "no luck",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "broken",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "fine",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "fine",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines()
+    );
+}
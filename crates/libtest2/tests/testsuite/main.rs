@@ -1,7 +1,27 @@
 mod all_passing;
 mod argfile;
+mod async_trial;
+mod cancel;
+mod environment;
+mod failure_details;
+mod filter_expr;
+mod history;
+mod hooks;
+mod interrupt;
+mod json_logfile;
 mod mixed_bag;
 mod panic;
+mod parametrize;
+mod progress;
+mod record_error;
+mod report_time;
+mod rerun_failed;
+mod scratch;
+mod seed;
+mod subcase;
+mod tags;
+mod timings;
+mod tree;
 mod util;
 
 pub use util::*;
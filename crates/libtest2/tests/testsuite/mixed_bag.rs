@@ -94,6 +94,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -112,6 +114,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -145,6 +149,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -163,6 +169,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -196,6 +204,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -214,6 +224,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -425,6 +437,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 2 failed; 0 ignored; 4 filtered out; finished in [..]s
 
 
@@ -447,6 +461,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 2 failed; 0 ignored; 4 filtered out; finished in [..]s
 
 
@@ -480,6 +496,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 2 failed; 0 ignored; 4 filtered out; finished in [..]s
 
 
@@ -502,6 +520,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 2 failed; 0 ignored; 4 filtered out; finished in [..]s
 
 
@@ -547,6 +567,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 4 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -577,6 +599,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 4 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -622,6 +646,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 4 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -652,6 +678,8 @@ failures:
     dog
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 4 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -679,6 +707,8 @@ broke neck
 failures:
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 1 passed; 1 failed; 0 ignored; 6 filtered out; finished in [..]s
 
 
@@ -697,6 +727,8 @@ broke neck
 failures:
     owl
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 1 passed; 1 failed; 0 ignored; 6 filtered out; finished in [..]s
 
 
@@ -714,6 +746,18 @@ fn list_json() {
 [
   {
     "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "--list",
+      "a",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
     "elapsed_s": "[..]"
   },
   {
@@ -774,6 +818,16 @@ fn list_json() {
 [
   {
     "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "--list",
+      "a"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
     "elapsed_s": "[..]"
   },
   {
@@ -843,6 +897,17 @@ fn test_json() {
 [
   {
     "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "a",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
     "elapsed_s": "[..]"
   },
   {
@@ -897,6 +962,8 @@ fn test_json() {
   },
   {
     "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
     "elapsed_s": "[..]"
   },
   {
@@ -946,8 +1013,17 @@ fn test_json() {
     "name": "cat"
   },
   {
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "a"
+    ],
+    "binary": "[..]",
+    "current_dir": "[..]",
     "elapsed_s": "[..]",
-    "event": "discover_start"
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "target": "[..]"
   },
   {
     "elapsed_s": "[..]",
@@ -971,6 +1047,8 @@ fn test_json() {
   },
   {
     "event": "run_start",
+    "seed": "[..]",
+    "threads": "{...}",
     "elapsed_s": "[..]"
   },
   {
@@ -1051,6 +1129,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -1068,6 +1148,8 @@ was not a good boy
 failures:
     dog
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 2 passed; 1 failed; 5 ignored; 0 filtered out; finished in [..]s
 
 
@@ -1087,6 +1169,10 @@ test bear  ... ignored
 test bunny ... ignored
 test cat   ... ok
 test dog   ... FAILED
+test fly   ... not run
+test fox   ... not run
+test frog  ... not run
+test owl   ... not run
 
 failures:
 
@@ -1097,7 +1183,9 @@ was not a good boy
 failures:
     dog
 
-test result: FAILED. 1 passed; 1 failed; 2 ignored; 0 filtered out; finished in [..]s
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 1 failed; 2 ignored; 0 filtered out; 4 not run; finished in [..]s
 
 
 "#]],
@@ -1118,6 +1206,17 @@ fn fail_fast_json() {
 [
   {
     "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "--fail-fast",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
     "elapsed_s": "[..]"
   },
   {
@@ -1166,6 +1265,8 @@ fn fail_fast_json() {
   },
   {
     "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
     "elapsed_s": "[..]"
   },
   {
@@ -1229,6 +1330,50 @@ fn fail_fast_json() {
     "name": "dog",
     "elapsed_s": "[..]"
   },
+  {
+    "event": "case_message",
+    "name": "fly",
+    "kind": "not_run",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "fly",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "fox",
+    "kind": "not_run",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "fox",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "frog",
+    "kind": "not_run",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "frog",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "owl",
+    "kind": "not_run",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "owl",
+    "elapsed_s": "[..]"
+  },
   {
     "event": "run_complete",
     "elapsed_s": "[..]"
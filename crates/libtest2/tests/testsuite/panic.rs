@@ -22,7 +22,9 @@ fn panics(_context: &libtest2::TestContext) -> libtest2::RunResult {
         let bin = crate::util::compile_test(&package_root);
         (bin, package_root)
     });
-    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+    snapbox::cmd::Command::new(bin)
+        .current_dir(current_dir)
+        .env_remove("RUST_BACKTRACE")
 }
 
 fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
@@ -54,11 +56,14 @@ failures:
 
 ---- panics ----
 test panicked: uh oh
+at test.rs:9:5
 
 
 failures:
     panics
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
@@ -72,11 +77,14 @@ failures:
 
 ---- panics ----
 test panicked: uh oh
+at test.rs:9:5
 
 
 failures:
     panics
 
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
 test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
 
 
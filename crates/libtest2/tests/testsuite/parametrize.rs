@@ -0,0 +1,520 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::Params;
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(Trial::parametrize(
+            "parse",
+            Params::new("input", [("empty", ""), ("digits", "42"), ("word", "hello")]),
+            |_context, input| {
+                input.parse::<u32>().map_err(RunError::fail)?;
+                Ok(())
+            },
+        ))
+        .cases(Trial::parametrize(
+            "radix",
+            Params::new("input", [("ones", "11"), ("nines", "99")])
+                .product("radix", [(2, 2), (10, 10)]),
+            |_context, (input, radix)| {
+                u32::from_str_radix(input, *radix).map_err(RunError::fail)?;
+                Ok(())
+            },
+        ))
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
+    test_cmd()
+        .args(args)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(code)
+        .stdout_eq(single);
+    test_cmd()
+        .args(args)
+        .assert()
+        .code(code)
+        .stdout_eq(parallel);
+}
+
+#[test]
+fn normal() {
+    check(
+        &[],
+        101,
+        str![[r#"
+
+running 7 tests
+test parse::digits    ... ok
+test parse::empty     ... FAILED
+test parse::word      ... FAILED
+test radix::nines::10 ... ok
+test radix::nines::2  ... FAILED
+test radix::ones::10  ... ok
+test radix::ones::2   ... ok
+
+failures:
+
+---- parse::empty ----
+parameters: input=empty
+cannot parse integer from empty string
+
+---- parse::word ----
+parameters: input=word
+invalid digit found in string
+
+---- radix::nines::2 ----
+parameters: input=nines, radix=2
+invalid digit found in string
+
+
+failures:
+    parse::empty
+    parse::word
+    radix::nines::2
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 4 passed; 3 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 7 tests
+test parse::digits    ... ok
+test parse::empty     ... FAILED
+test parse::word      ... FAILED
+test radix::nines::10 ... ok
+test radix::nines::2  ... FAILED
+test radix::ones::10  ... ok
+test radix::ones::2   ... ok
+
+failures:
+
+---- parse::empty ----
+parameters: input=empty
+cannot parse integer from empty string
+
+---- parse::word ----
+parameters: input=word
+invalid digit found in string
+
+---- radix::nines::2 ----
+parameters: input=nines, radix=2
+invalid digit found in string
+
+
+failures:
+    parse::empty
+    parse::word
+    radix::nines::2
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 4 passed; 3 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+fn list() {
+    test_cmd()
+        .args(["--list"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+parse::digits: test
+parse::empty: test
+parse::word: test
+radix::nines::10: test
+radix::nines::2: test
+radix::ones::10: test
+radix::ones::2: test
+
+7 tests
+
+
+"#]]);
+}
+
+#[test]
+fn filter() {
+    check(
+        &["radix::nines"],
+        101,
+        str![[r#"
+
+running 2 tests
+test radix::nines::10 ... ok
+test radix::nines::2  ... FAILED
+
+failures:
+
+---- radix::nines::2 ----
+parameters: input=nines, radix=2
+invalid digit found in string
+
+
+failures:
+    radix::nines::2
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 5 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 2 tests
+test radix::nines::10 ... ok
+test radix::nines::2  ... FAILED
+
+failures:
+
+---- radix::nines::2 ----
+parameters: input=nines, radix=2
+invalid digit found in string
+
+
+failures:
+    radix::nines::2
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 5 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    check(
+        &["-Zunstable-options", "--format=json", "radix::ones::2"],
+        0,
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "radix::ones::2",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "parse::digits",
+    "selected": false,
+    "params": {
+      "input": "digits"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "parse::empty",
+    "selected": false,
+    "params": {
+      "input": "empty"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "parse::word",
+    "selected": false,
+    "params": {
+      "input": "word"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "radix::nines::10",
+    "selected": false,
+    "params": {
+      "input": "nines",
+      "radix": "10"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "radix::nines::2",
+    "selected": false,
+    "params": {
+      "input": "nines",
+      "radix": "2"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "radix::ones::10",
+    "selected": false,
+    "params": {
+      "input": "ones",
+      "radix": "10"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "radix::ones::2",
+    "params": {
+      "input": "ones",
+      "radix": "2"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "radix::ones::2",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "radix::ones::2",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "radix::ones::2"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "parse::digits",
+    "selected": false,
+    "params": {
+      "input": "digits"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "parse::empty",
+    "selected": false,
+    "params": {
+      "input": "empty"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "parse::word",
+    "selected": false,
+    "params": {
+      "input": "word"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "radix::nines::10",
+    "selected": false,
+    "params": {
+      "input": "nines",
+      "radix": "10"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "radix::nines::2",
+    "selected": false,
+    "params": {
+      "input": "nines",
+      "radix": "2"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "radix::ones::10",
+    "selected": false,
+    "params": {
+      "input": "ones",
+      "radix": "10"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "radix::ones::2",
+    "params": {
+      "input": "ones",
+      "radix": "2"
+    },
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": "{...}",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "radix::ones::2",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "radix::ones::2",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+    );
+}
+
+fn labels_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::Params;
+use libtest2::Trial;
+
+fn main() {
+    let labels = std::env::var("LABELS").unwrap();
+    let labels = labels.split(',').map(|label| (label.to_owned(), ()));
+    libtest2::Harness::with_env()
+        .cases(Trial::parametrize(
+            "case",
+            Params::new("input", labels),
+            |_context, _input| Ok(()),
+        ))
+        .main();
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+    snapbox::cmd::Command::new(bin).env_remove("RUST_BACKTRACE")
+}
+
+#[test]
+fn invalid_label() {
+    labels_cmd()
+        .env("LABELS", "one,two words")
+        .assert()
+        .code(101)
+        .stderr_eq(str![[r#"
+
+thread 'main'[..]panicked at [..]:
+parameter `input` has the invalid label `two words`, labels must be non-empty without whitespace or `::`
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+"#]]);
+    labels_cmd()
+        .env("LABELS", "one,a::b")
+        .assert()
+        .code(101)
+        .stderr_eq(str![[r#"
+
+thread 'main'[..]panicked at [..]:
+parameter `input` has the invalid label `a::b`, labels must be non-empty without whitespace or `::`
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+"#]]);
+    labels_cmd()
+        .env("LABELS", "one,")
+        .assert()
+        .code(101)
+        .stderr_eq(str![[r#"
+
+thread 'main'[..]panicked at [..]:
+parameter `input` has the invalid label ``, labels must be non-empty without whitespace or `::`
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+"#]]);
+}
+
+#[test]
+fn duplicate_label() {
+    labels_cmd()
+        .env("LABELS", "one,two,one")
+        .assert()
+        .code(101)
+        .stderr_eq(str![[r#"
+
+thread 'main'[..]panicked at [..]:
+parameter `input` has the label `one` more than once
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+"#]]);
+}
@@ -0,0 +1,59 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("fast", |_context| Ok(())),
+            Trial::test("broken", |_context| Err(RunError::fail("no luck"))),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+}
+
+#[test]
+fn not_a_terminal() {
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--format=progress",
+            "--test-threads=1",
+        ])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+test broken ... FAILED
+test fast   ... ok
+
+failures:
+
+---- broken ----
+no luck
+
+
+failures:
+    broken
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
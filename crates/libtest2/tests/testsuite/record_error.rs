@@ -0,0 +1,341 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+libtest2::libtest2_main!(all_good, one_bad, many_bad, bad_then_panic, bad_then_ignore);
+
+fn all_good(context: &libtest2::TestContext) -> libtest2::RunResult {
+    for i in [2, 4, 6] {
+        if i % 2 != 0 {
+            context.record_error(libtest2::RunError::fail(format_args!("{i} is odd")));
+        }
+    }
+    Ok(())
+}
+
+fn one_bad(context: &libtest2::TestContext) -> libtest2::RunResult {
+    for i in [2, 3, 4] {
+        if i % 2 != 0 {
+            context.record_error(libtest2::RunError::fail(format_args!("{i} is odd")));
+        }
+    }
+    Ok(())
+}
+
+fn many_bad(context: &libtest2::TestContext) -> libtest2::RunResult {
+    for i in [1, 2, 3] {
+        if i % 2 != 0 {
+            context.record_error(libtest2::RunError::fail(format_args!("{i} is odd")));
+        }
+    }
+    Ok(())
+}
+
+fn bad_then_panic(context: &libtest2::TestContext) -> libtest2::RunResult {
+    context.record_error(libtest2::RunError::fail("first"));
+    panic!("second")
+}
+
+fn bad_then_ignore(context: &libtest2::TestContext) -> libtest2::RunResult {
+    context.record_error(libtest2::RunError::fail("first"));
+    context.ignore()?;
+    Ok(())
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+        .current_dir(current_dir)
+        .env_remove("RUST_BACKTRACE")
+}
+
+fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
+    test_cmd()
+        .args(args)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(code)
+        .stdout_eq(single);
+    test_cmd()
+        .args(args)
+        .assert()
+        .code(code)
+        .stdout_eq(parallel);
+}
+
+#[test]
+fn normal() {
+    check(
+        &[],
+        101,
+        str![[r#"
+
+running 5 tests
+test all_good        ... ok
+test bad_then_ignore ... FAILED
+test bad_then_panic  ... FAILED
+test many_bad        ... FAILED
+test one_bad         ... FAILED
+
+failures:
+
+---- bad_then_ignore ----
+first
+
+---- bad_then_panic ----
+first
+
+test panicked: second
+at test.rs:33:5
+
+---- many_bad ----
+1 is odd
+
+3 is odd
+
+---- one_bad ----
+3 is odd
+
+
+failures:
+    bad_then_ignore
+    bad_then_panic
+    many_bad
+    one_bad
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 5 tests
+...
+
+failures:
+
+---- bad_then_ignore ----
+first
+
+---- bad_then_panic ----
+first
+
+test panicked: second
+at test.rs:33:5
+
+---- many_bad ----
+1 is odd
+
+3 is odd
+
+---- one_bad ----
+3 is odd
+
+
+failures:
+    bad_then_ignore
+    bad_then_panic
+    many_bad
+    one_bad
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 4 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    check(
+        &["-Zunstable-options", "--format=json", "many_bad"],
+        101,
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "many_bad",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "all_good",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "bad_then_ignore",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "bad_then_panic",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "one_bad",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "many_bad",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "many_bad",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "many_bad",
+    "kind": "error",
+    "message": "1 is odd",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "many_bad",
+    "kind": "error",
+    "message": "3 is odd",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "many_bad",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "many_bad"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "all_good",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "bad_then_ignore",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "bad_then_panic",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "one_bad",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "many_bad",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": "{...}",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "many_bad",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "many_bad",
+    "kind": "error",
+    "message": "1 is odd",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "many_bad",
+    "kind": "error",
+    "message": "3 is odd",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "many_bad",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+    );
+}
@@ -0,0 +1,112 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn sleep(millis: u64) {
+    std::thread::sleep(std::time::Duration::from_millis(millis));
+}
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("a_fast", |_context| Ok(())),
+            Trial::test("b_slow", |_context| {
+                sleep(300);
+                Ok(())
+            }),
+            Trial::test("c_slower", |_context| {
+                sleep(600);
+                Err(RunError::fail("too slow"))
+            }),
+            Trial::test("d_fast", |_context| Ok(())),
+            Trial::test("e_fast", |_context| Ok(())),
+            Trial::test("f_fast", |_context| Ok(())),
+            Trial::test("g_fast", |_context| Ok(())),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+}
+
+#[test]
+fn pretty() {
+    test_cmd()
+        .args(["-Zunstable-options", "--report-time", "--test-threads=1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 7 tests
+test a_fast   ... ok <[..]s>
+test b_slow   ... ok <[..]s>
+test c_slower ... FAILED <[..]s>
+test d_fast   ... ok <[..]s>
+test e_fast   ... ok <[..]s>
+test f_fast   ... ok <[..]s>
+test g_fast   ... ok <[..]s>
+
+failures:
+
+---- c_slower ----
+too slow
+
+
+failures:
+    c_slower
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+slowest tests:
+    [..]s c_slower
+    [..]s b_slow
+...
+
+test result: FAILED. 6 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn terse() {
+    test_cmd()
+        .args(["-Zunstable-options", "--report-time", "--quiet"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 7 tests
+..F....
+failures:
+
+---- c_slower ----
+too slow
+
+
+failures:
+    c_slower
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+slowest tests:
+    [..]s c_slower
+    [..]s b_slow
+...
+
+test result: FAILED. 6 passed; 1 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
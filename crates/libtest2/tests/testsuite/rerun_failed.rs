@@ -0,0 +1,171 @@
+use snapbox::str;
+
+fn test_bin() -> &'static (std::path::PathBuf, std::path::PathBuf) {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .scratch_root("scratch")
+        .cases(vec![
+            Trial::test("a_passes", |_context| Ok(())),
+            Trial::test("b_fails", |_context| Err(RunError::fail("no luck"))),
+            Trial::test("c_fails", |_context| Err(RunError::fail("no luck"))),
+            Trial::test("d_passes", |_context| Ok(())),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    })
+}
+
+fn test_cmd() -> snapbox::cmd::Command {
+    let (bin, current_dir) = test_bin();
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn rerun_failed() {
+    let (_, current_dir) = test_bin();
+    let failures = current_dir.join("scratch/failed.txt");
+    let _ = std::fs::remove_file(&failures);
+
+    test_cmd()
+        .args(["-Zunstable-options", "--rerun-failed"])
+        .assert()
+        .code(1)
+        .stderr_eq(str![[r#"
+no failures recorded at scratch/failed.txt
+
+"#]]);
+
+    test_cmd()
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 4 tests
+test a_passes ... ok
+test b_fails  ... FAILED
+test c_fails  ... FAILED
+test d_passes ... ok
+
+failures:
+
+---- b_fails ----
+no luck
+
+---- c_fails ----
+no luck
+
+
+failures:
+    b_fails
+    c_fails
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 2 passed; 2 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(&failures).unwrap(),
+        str![[r#"
+b_fails
+c_fails
+
+"#]]
+    );
+
+    // Run in the recorded order
+    std::fs::write(&failures, "c_fails\nb_fails\n").unwrap();
+    test_cmd()
+        .args([
+            "-Zunstable-options",
+            "--rerun-failed",
+            "--test-threads",
+            "1",
+        ])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+test c_fails ... FAILED
+test b_fails ... FAILED
+
+failures:
+
+---- b_fails ----
+no luck
+
+---- c_fails ----
+no luck
+
+
+failures:
+    b_fails
+    c_fails
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 0 passed; 2 failed; 0 ignored; 2 filtered out; finished in [..]s
+
+
+"#]]);
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(&failures).unwrap(),
+        str![[r#"
+c_fails
+b_fails
+
+"#]]
+    );
+
+    // Nothing failed so there is nothing left to rerun
+    test_cmd().args(["a_passes"]).assert().success();
+    assert!(!failures.exists());
+}
+
+#[test]
+fn rerun_command() {
+    // Keep clear of the failures recorded by other tests
+    let (bin, package_root) = test_bin();
+    let current_dir = package_root.join("rerun_command");
+    std::fs::create_dir_all(&current_dir).unwrap();
+    let test_cmd = || snapbox::cmd::Command::new(bin).current_dir(&current_dir);
+
+    let output = test_cmd().args(["--test-threads", "1"]).output().unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let command = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("to rerun the failures, run `"))
+        .and_then(|line| line.strip_suffix('`'))
+        .unwrap_or_else(|| panic!("no rerun command:\n{stdout}"));
+    let mut command = command.split(' ');
+    assert_eq!(std::path::Path::new(command.next().unwrap()), bin);
+
+    test_cmd()
+        .args(command)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+test b_fails ... FAILED
+test c_fails ... FAILED
+...
+"#]]);
+}
@@ -0,0 +1,342 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+/// Cases share a scratch root, so don't run them concurrently
+static SCRATCH_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+fn test_bin() -> &'static (std::path::PathBuf, std::path::PathBuf) {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .scratch_root("scratch")
+        .cases(vec![
+            Trial::test("passes", |context| {
+                let dir = context.scratch_dir()?;
+                std::fs::write(dir.join("out.txt"), "passes")?;
+                Ok(())
+            }),
+            Trial::test("nested::fails", |context| {
+                let dir = context.scratch_dir()?;
+                std::fs::write(dir.join("out.txt"), "fails")?;
+                Err(libtest2::RunError::fail("bad output"))
+            }),
+            Trial::test("unused", |_context| Err(libtest2::RunError::fail("no files"))),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    })
+}
+
+fn test_cmd() -> snapbox::cmd::Command {
+    let (bin, current_dir) = test_bin();
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
+    test_cmd()
+        .args(args)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(code)
+        .stdout_eq(single);
+    test_cmd()
+        .args(args)
+        .assert()
+        .code(code)
+        .stdout_eq(parallel);
+}
+
+#[test]
+fn normal() {
+    let _guard = SCRATCH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let root = test_bin().1.join("scratch");
+    let _ = std::fs::remove_dir_all(&root);
+    check(
+        &[],
+        101,
+        str![[r#"
+
+running 3 tests
+test nested::fails ... FAILED
+test passes        ... ok
+test unused        ... FAILED
+
+failures:
+
+---- nested::fails ----
+bad output
+scratch directory retained at scratch/nested__fails-e125466e519f8b37
+
+---- unused ----
+no files
+
+
+failures:
+    nested::fails
+    unused
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 3 tests
+...
+
+failures:
+
+---- nested::fails ----
+bad output
+scratch directory retained at scratch/nested__fails-e125466e519f8b37
+
+---- unused ----
+no files
+
+
+failures:
+    nested::fails
+    unused
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]],
+    );
+
+    let mut retained = std::fs::read_dir(&root)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_dir())
+        .collect::<Vec<_>>();
+    assert_eq!(retained.len(), 1, "{retained:?}");
+    let retained = retained.pop().unwrap();
+    assert!(
+        retained
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("nested__fails-"),
+        "{retained:?}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(retained.join("out.txt")).unwrap(),
+        "fails"
+    );
+}
+
+#[test]
+fn separate_dirs() {
+    let package_root = crate::util::new_test(
+        r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn check_kept(dir: &std::path::Path, content: &str) -> libtest2::RunResult {
+    std::fs::write(dir.join("out.txt"), content)?;
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    match std::fs::read_to_string(dir.join("out.txt")) {
+        Ok(actual) if actual == content => Ok(()),
+        Ok(actual) => Err(RunError::fail(format_args!("overwritten with `{actual}`"))),
+        Err(err) => Err(RunError::fail(format_args!("removed: {err}"))),
+    }
+}
+
+fn main() {
+    libtest2::Harness::with_env()
+        .scratch_root("scratch")
+        .cases(vec![
+            Trial::test("a::b", |context| check_kept(context.scratch_dir()?, "a::b")),
+            Trial::test("a", |context| {
+                // Let `a::b` create its directory first
+                std::thread::sleep(std::time::Duration::from_millis(200));
+                std::fs::write(context.scratch_dir()?.join("out.txt"), "a")?;
+                Ok(())
+            }),
+            Trial::test("x y", |context| check_kept(context.scratch_dir()?, "x y")),
+            Trial::test("x_y", |context| check_kept(context.scratch_dir()?, "x_y")),
+        ])
+        .main();
+}
+"#,
+        false,
+    );
+    let bin = crate::util::compile_test(&package_root);
+    snapbox::cmd::Command::new(bin)
+        .current_dir(&package_root)
+        .args(["--test-threads", "4"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 4 tests
+...
+
+test result: ok. 4 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    let _guard = SCRATCH_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    check(
+        &["-Zunstable-options", "--format=json", "nested"],
+        101,
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "nested",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "passes",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "unused",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "nested::fails",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "nested::fails",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "nested::fails",
+    "kind": "error",
+    "message": "bad output",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "nested::fails",
+    "scratch_dir": "scratch/nested__fails-e125466e519f8b37",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "nested"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "passes",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "unused",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "nested::fails",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": "{...}",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "nested::fails",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "nested::fails",
+    "kind": "error",
+    "message": "bad output",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "nested::fails",
+    "scratch_dir": "scratch/nested__fails-e125466e519f8b37",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+    );
+}
@@ -0,0 +1,83 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn report_seed(context: &libtest2::TestContext) -> libtest2::RunResult {
+    context.subcase("sub", |context| {
+        Err(RunError::fail(format_args!("seed {}", context.seed())))
+    });
+    Err(RunError::fail(format_args!("seed {}", context.seed())))
+}
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("one", report_seed),
+            Trial::test("two", report_seed),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin).current_dir(current_dir)
+}
+
+#[test]
+fn seeded() {
+    test_cmd()
+        .args(["-Zunstable-options", "--seed", "42", "--test-threads", "1"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+test one ... 
+    sub ... FAILED
+test one ... FAILED
+test two ... 
+    sub ... FAILED
+test two ... FAILED
+
+failures:
+
+---- one ----
+sub-case `one::sub` failed
+
+seed 11360157391075366466
+
+---- one::sub ----
+seed 742664796783912423
+
+---- two ----
+sub-case `two::sub` failed
+
+seed 7207937598254580777
+
+---- two::sub ----
+seed 13503558030918368671
+
+
+failures:
+    one
+    one::sub
+    two
+    two::sub
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed 42`
+
+test result: FAILED. 0 passed; 2 failed; 0 ignored; 0 filtered out; 2 sub-cases (0 passed, 2 failed, 0 ignored); finished in [..]s
+
+
+"#]]);
+}
@@ -0,0 +1,376 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let (bin, current_dir) = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+libtest2::libtest2_main!(table, all_pass, nested);
+
+fn table(context: &libtest2::TestContext) -> libtest2::RunResult {
+    for (name, input, expected) in [("one", "1", 1), ("two", "2", 2), ("bad", "x", 0)] {
+        context.subcase(name, |_| {
+            let actual = input.parse::<i32>()?;
+            assert_eq!(actual, expected);
+            Ok(())
+        });
+    }
+    Ok(())
+}
+
+fn all_pass(context: &libtest2::TestContext) -> libtest2::RunResult {
+    context.subcase("a", |_| Ok(()));
+    context.subcase("b", |context| {
+        context.ignore_for("not today")?;
+        Ok(())
+    });
+    Ok(())
+}
+
+fn nested(context: &libtest2::TestContext) -> libtest2::RunResult {
+    context.subcase("outer", |context| {
+        context.subcase("inner", |_| panic!("deep"));
+        Ok(())
+    });
+    Ok(())
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    });
+    snapbox::cmd::Command::new(bin)
+        .current_dir(current_dir)
+        .env_remove("RUST_BACKTRACE")
+}
+
+fn check(args: &[&str], code: i32, single: impl IntoData, parallel: impl IntoData) {
+    test_cmd()
+        .args(args)
+        .args(["--test-threads", "1"])
+        .assert()
+        .code(code)
+        .stdout_eq(single);
+    test_cmd()
+        .args(args)
+        .assert()
+        .code(code)
+        .stdout_eq(parallel);
+}
+
+#[test]
+fn normal() {
+    check(
+        &[],
+        101,
+        str![[r#"
+
+running 3 tests
+test all_pass ... 
+    a ... ok
+    b ... ignored
+test all_pass ... ok
+test nested   ... 
+    outer ... 
+        inner ... FAILED
+    outer ... FAILED
+test nested   ... FAILED
+test table    ... 
+    one ... ok
+    two ... ok
+    bad ... FAILED
+test table    ... FAILED
+
+failures:
+
+---- nested ----
+sub-case `nested::outer` failed
+
+---- nested::outer ----
+sub-case `nested::outer::inner` failed
+
+---- nested::outer::inner ----
+test panicked: deep
+at test.rs:26:38
+
+---- table ----
+sub-case `table::bad` failed
+
+---- table::bad ----
+invalid digit found in string
+
+
+failures:
+    nested
+    nested::outer
+    nested::outer::inner
+    table
+    table::bad
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 filtered out; 7 sub-cases (3 passed, 3 failed, 1 ignored); finished in [..]s
+
+
+"#]],
+        str![[r#"
+
+running 3 tests
+...
+
+failures:
+
+---- nested ----
+sub-case `nested::outer` failed
+
+---- nested::outer ----
+sub-case `nested::outer::inner` failed
+
+---- nested::outer::inner ----
+test panicked: deep
+at test.rs:26:38
+
+---- table ----
+sub-case `table::bad` failed
+
+---- table::bad ----
+invalid digit found in string
+
+
+failures:
+    nested
+    nested::outer
+    nested::outer::inner
+    table
+    table::bad
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 filtered out; 7 sub-cases (3 passed, 3 failed, 1 ignored); finished in [..]s
+
+
+"#]],
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    check(
+        &["-Zunstable-options", "--format=json", "nested"],
+        101,
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "nested",
+      "--test-threads",
+      "1"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "all_pass",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "table",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "nested",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": 1,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "nested",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "nested::outer",
+    "parent": "nested",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "nested::outer::inner",
+    "parent": "nested::outer",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "nested::outer::inner",
+    "kind": "error",
+    "message": "test panicked: deep",
+    "location": "test.rs:26:38",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "nested::outer::inner",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "nested::outer",
+    "kind": "error",
+    "message": "sub-case `nested::outer::inner` failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "nested::outer",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "nested",
+    "kind": "error",
+    "message": "sub-case `nested::outer` failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "nested",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+        str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--format=json",
+      "nested"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "all_pass",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "table",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "nested",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_start",
+    "seed": "[..]",
+    "threads": "{...}",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "nested",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "nested::outer",
+    "parent": "nested",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_start",
+    "name": "nested::outer::inner",
+    "parent": "nested::outer",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "nested::outer::inner",
+    "kind": "error",
+    "message": "test panicked: deep",
+    "location": "test.rs:26:38",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "nested::outer::inner",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "nested::outer",
+    "kind": "error",
+    "message": "sub-case `nested::outer::inner` failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "nested::outer",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_message",
+    "name": "nested",
+    "kind": "error",
+    "message": "sub-case `nested::outer` failed",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "case_complete",
+    "name": "nested",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "run_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+        .is_json()
+        .against_jsonlines(),
+    );
+}
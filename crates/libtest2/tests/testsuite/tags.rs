@@ -0,0 +1,146 @@
+use snapbox::prelude::*;
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("fetch", |_context| Ok(()))
+                .with_tag("slow")
+                .with_tag("network"),
+            Trial::test("parse", |_context| Ok(())),
+            Trial::test("render", |_context| Ok(())).with_tag("slow"),
+            Trial::test("build_image", |_context| Ok(())).with_tag("requires-docker"),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+    snapbox::cmd::Command::new(bin).args(["-Zunstable-options", "--list"])
+}
+
+#[test]
+fn tag() {
+    test_cmd()
+        .args(["--tag", "slow", "--tag", "requires-docker"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+build_image: test
+fetch: test
+render: test
+
+3 tests
+
+
+"#]]);
+}
+
+#[test]
+fn skip_tag() {
+    test_cmd()
+        .args(["--skip-tag", "network", "--skip-tag", "requires-docker"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+parse: test
+render: test
+
+2 tests
+
+
+"#]]);
+}
+
+#[test]
+fn with_filters() {
+    test_cmd()
+        .args(["--tag", "slow", "--skip-tag", "network", "r"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+render: test
+
+1 tests
+
+
+"#]]);
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    test_cmd()
+        .args(["--format=json", "--tag", "slow"])
+        .assert()
+        .success()
+        .stdout_eq(
+            str![[r#"
+[
+  {
+    "event": "discover_start",
+    "started_at_s": "[..]",
+    "binary": "[..]",
+    "args": [
+      "-Zunstable-options",
+      "--list",
+      "--format=json",
+      "--tag",
+      "slow"
+    ],
+    "current_dir": "[..]",
+    "target": "[..]",
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "build_image",
+    "selected": false,
+    "tags": [
+      "requires-docker"
+    ],
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "fetch",
+    "tags": [
+      "slow",
+      "network"
+    ],
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "parse",
+    "selected": false,
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_case",
+    "name": "render",
+    "tags": [
+      "slow"
+    ],
+    "elapsed_s": "[..]"
+  },
+  {
+    "event": "discover_complete",
+    "elapsed_s": "[..]"
+  }
+]
+"#]]
+            .is_json()
+            .against_jsonlines(),
+        );
+}
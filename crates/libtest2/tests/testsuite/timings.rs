@@ -0,0 +1,146 @@
+use snapbox::str;
+
+fn test_bin() -> &'static (std::path::PathBuf, std::path::PathBuf) {
+    static BIN: once_cell_polyfill::sync::OnceLock<(std::path::PathBuf, std::path::PathBuf)> =
+        once_cell_polyfill::sync::OnceLock::new();
+    BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::Trial;
+
+fn case(name: &'static str) -> Trial {
+    Trial::test(name, move |_| {
+        use std::io::Write as _;
+        let mut order = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open("order.txt")?;
+        writeln!(order, "{name}")?;
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        Ok(())
+    })
+}
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(["a", "b", "c", "d"].map(case))
+        .main();
+}
+"#,
+            false,
+        );
+        let bin = crate::util::compile_test(&package_root);
+        (bin, package_root)
+    })
+}
+
+/// Run in a fresh directory, returning the order cases started in, in pairs
+fn run(name: &str, timings: Option<&str>, args: &[&str]) -> (std::path::PathBuf, Vec<Vec<String>>) {
+    let (bin, package_root) = test_bin();
+    let dir = package_root.join(name);
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    if let Some(timings) = timings {
+        std::fs::write(dir.join("timings.txt"), timings).unwrap();
+    }
+
+    snapbox::cmd::Command::new(bin)
+        .current_dir(&dir)
+        .args(["-Zunstable-options", "--timings", "timings.txt"])
+        .args(["--test-threads", "2"])
+        .args(args)
+        .assert()
+        .success();
+
+    // Two cases start at a time, in either order
+    let order = std::fs::read_to_string(dir.join("order.txt")).unwrap();
+    let mut order = order.lines().map(|l| l.to_owned()).collect::<Vec<_>>();
+    for pair in order.chunks_mut(2) {
+        pair.sort();
+    }
+    let order = order.chunks(2).map(|pair| pair.to_vec()).collect();
+    (dir, order)
+}
+
+#[test]
+fn records_timings() {
+    let (dir, order) = run("records_timings", None, &[]);
+    assert_eq!(order, [["a", "b"], ["c", "d"]]);
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(dir.join("timings.txt")).unwrap(),
+        str![[r#"
+0.1[..]	a
+0.1[..]	b
+0.1[..]	c
+0.1[..]	d
+
+"#]]
+    );
+}
+
+#[test]
+fn slowest_first() {
+    let (dir, order) = run(
+        "slowest_first",
+        Some("0.001\ta\n2.000\tc\n3.000\td\n1.000\tgone\n"),
+        &[],
+    );
+    // `b` has no timings, so it might be slow
+    assert_eq!(order, [["b", "d"], ["a", "c"]]);
+    snapbox::assert_data_eq!(
+        std::fs::read_to_string(dir.join("timings.txt")).unwrap(),
+        str![[r#"
+0.1[..]	a
+0.1[..]	b
+0.1[..]	c
+0.1[..]	d
+1.000	gone
+
+"#]]
+    );
+}
+
+#[test]
+fn filter_order_wins() {
+    let (_dir, order) = run(
+        "filter_order_wins",
+        Some("0.001\ta\n2.000\tc\n3.000\td\n"),
+        &["a", "c", "b", "d"],
+    );
+    assert_eq!(order, [["a", "c"], ["b", "d"]]);
+}
+
+#[test]
+fn invalid_timings() {
+    let (bin, package_root) = test_bin();
+    let dir = package_root.join("invalid_timings");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("timings.txt"), "a 1.0\n").unwrap();
+    snapbox::cmd::Command::new(bin)
+        .current_dir(&dir)
+        .args(["-Zunstable-options", "--timings", "timings.txt"])
+        .assert()
+        .code(1)
+        .stderr_eq(str![[r#"
+timings.txt:1: expected seconds and a case name, separated by a tab
+
+"#]]);
+}
+
+#[test]
+fn unsaved_timings() {
+    let (bin, package_root) = test_bin();
+    let dir = package_root.join("unsaved_timings");
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    snapbox::cmd::Command::new(bin)
+        .current_dir(&dir)
+        .args(["-Zunstable-options", "--timings", "missing/timings.txt"])
+        .args(["--test-threads", "1", "a"])
+        .assert()
+        .success()
+        .stderr_eq(str![[r#"
+warning: failed to save timings to missing/timings.txt: [..]
+
+"#]]);
+}
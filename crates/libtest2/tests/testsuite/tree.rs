@@ -0,0 +1,216 @@
+use snapbox::str;
+
+fn test_cmd() -> snapbox::cmd::Command {
+    static BIN: once_cell_polyfill::sync::OnceLock<std::path::PathBuf> =
+        once_cell_polyfill::sync::OnceLock::new();
+    let bin = BIN.get_or_init(|| {
+        let package_root = crate::util::new_test(
+            r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("cli::help", |_context| Ok(())),
+            Trial::test("cli::version", |_context| Ok(())),
+            Trial::test("parser::expr::add", |_context| Ok(())),
+            Trial::test("parser::expr::deep", |_context| Err(RunError::fail("too deep"))),
+            Trial::test("parser::stmt::let_else", |context| context.ignore()),
+            Trial::test("parser::stmt::while_let", |_context| Ok(())),
+            Trial::test("smoke", |_context| Ok(())),
+        ])
+        .main();
+}
+"#,
+            false,
+        );
+        crate::util::compile_test(&package_root)
+    });
+    // Groups are printed as they complete
+    snapbox::cmd::Command::new(bin).args([
+        "-Zunstable-options",
+        "--format=tree",
+        "--test-threads",
+        "1",
+    ])
+}
+
+#[test]
+fn tree() {
+    test_cmd().assert().code(101).stdout_eq(str![[r#"
+
+running 7 tests
+cli ... 2 passed
+parser ... 2 passed; 1 failed; 1 ignored
+    expr ... 1 passed; 1 failed
+        add ... ok
+        deep ... FAILED
+    stmt ... 1 passed; 1 ignored
+        let_else ... ignored
+        while_let ... ok
+smoke ... ok
+
+failures:
+
+---- parser::expr::deep ----
+too deep
+
+
+failures:
+    parser::expr::deep
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 5 passed; 1 failed; 1 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn passing() {
+    test_cmd()
+        .args(["--skip", "parser::expr::deep"])
+        .assert()
+        .success()
+        .stdout_eq(str![[r#"
+
+running 6 tests
+cli ... 2 passed
+parser ... 2 passed; 1 ignored
+    expr ... 1 passed
+    stmt ... 1 passed; 1 ignored
+        let_else ... ignored
+        while_let ... ok
+smoke ... ok
+
+test result: ok. 5 passed; 0 failed; 1 ignored; 1 filtered out; finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn subcases() {
+    let package_root = crate::util::new_test(
+        r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("rows::all", |context| {
+                context.subcase("first", |_context| Ok(()));
+                context.subcase("second", |_context| Ok(()));
+                Ok(())
+            }),
+            Trial::test("rows::some", |context| {
+                context.subcase("first", |_context| Ok(()));
+                context.subcase("second", |_context| Err(RunError::fail("no luck")));
+                Ok(())
+            }),
+        ])
+        .main();
+}
+"#,
+        false,
+    );
+    let bin = crate::util::compile_test(&package_root);
+    snapbox::cmd::Command::new(bin)
+        .args(["-Zunstable-options", "--format=tree"])
+        .assert()
+        .code(101)
+        .stdout_eq(str![[r#"
+
+running 2 tests
+rows ... 1 passed; 1 failed
+    all ... ok (2 passed)
+    some ... FAILED (1 passed; 1 failed)
+        first ... ok
+        second ... FAILED
+
+failures:
+
+---- rows::some ----
+sub-case `rows::some::second` failed
+
+---- rows::some::second ----
+no luck
+
+
+failures:
+    rows::some
+    rows::some::second
+
+to rerun the failures, run `[..] -Zunstable-options --rerun-failed --seed [..]`
+
+test result: FAILED. 1 passed; 1 failed; 0 ignored; 0 filtered out; 4 sub-cases (3 passed, 1 failed, 0 ignored); finished in [..]s
+
+
+"#]]);
+}
+
+#[test]
+fn streams_groups() {
+    use std::io::BufRead as _;
+    use std::io::Read as _;
+
+    let package_root = crate::util::new_test(
+        r#"
+use libtest2::RunError;
+use libtest2::Trial;
+
+fn main() {
+    libtest2::Harness::with_env()
+        .cases(vec![
+            Trial::test("early::a", |_context| Ok(())),
+            Trial::test("early::b", |_context| Ok(())),
+            Trial::test("late", |_context| {
+                // Only created once `early` was reported
+                for _ in 0..300 {
+                    if std::path::Path::new("go").exists() {
+                        return Ok(());
+                    }
+                    std::thread::sleep(std::time::Duration::from_millis(100));
+                }
+                Err(RunError::fail("`early` was never reported"))
+            }),
+        ])
+        .main();
+}
+"#,
+        false,
+    );
+    let bin = crate::util::compile_test(&package_root);
+    let _ = std::fs::remove_file(package_root.join("go"));
+    let mut child = std::process::Command::new(bin)
+        .args(["-Zunstable-options", "--format=tree", "--test-threads", "1"])
+        .current_dir(&package_root)
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdout = std::io::BufReader::new(child.stdout.take().unwrap());
+    let mut output = String::new();
+    while !output.contains("early ... 2 passed\n") {
+        let read = stdout.read_line(&mut output).unwrap();
+        assert_ne!(read, 0, "exited before `early` was reported:\n{output}");
+    }
+    std::fs::write(package_root.join("go"), "").unwrap();
+    stdout.read_to_string(&mut output).unwrap();
+    assert!(child.wait().unwrap().success(), "{output}");
+    snapbox::assert_data_eq!(
+        output,
+        str![[r#"
+
+running 3 tests
+early ... 2 passed
+late ... ok
+
+test result: ok. 3 passed; 0 failed; 0 ignored; 0 filtered out; finished in [..]s
+
+
+"#]]
+    );
+}
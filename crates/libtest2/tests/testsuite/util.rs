@@ -1,4 +1,8 @@
 pub fn new_test(test: &str, harness: bool) -> std::path::PathBuf {
+    new_test_with_features(test, harness, &[])
+}
+
+pub fn new_test_with_features(test: &str, harness: bool, features: &[&str]) -> std::path::PathBuf {
     static SUFFIX: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
     let suffix = SUFFIX.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
     let target_name = format!("t{suffix}");
@@ -7,6 +11,11 @@ pub fn new_test(test: &str, harness: bool) -> std::path::PathBuf {
 
     let mimic_relpath = mimic_relpath(&package_root);
     let mimic_relpath = mimic_relpath.display();
+    let features = features
+        .iter()
+        .map(|feature| format!("{feature:?}"))
+        .collect::<Vec<_>>()
+        .join(", ");
 
     std::fs::create_dir_all(&package_root).unwrap();
     std::fs::write(
@@ -24,7 +33,7 @@ edition = "2021"
 path = "lib.rs"
 
 [dev-dependencies]
-libtest2.path = "{mimic_relpath}"
+libtest2 = {{ path = "{mimic_relpath}", features = [{features}] }}
 
 [[test]]
 name = "{target_name}"